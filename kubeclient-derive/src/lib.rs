@@ -0,0 +1,267 @@
+//! `#[derive(CustomResource)]` for kubeclient.
+//!
+//! The derive is placed on the spec of a custom resource and generates the resource type itself,
+//! named after the kind, along with its list type and the `KubeKind` and `CustomResource` impls:
+//!
+//! ```
+//! # #[macro_use] extern crate serde_derive;
+//! # use kubeclient::CustomResource;
+//! # use kubeclient::api::KubeKind;
+//! #[derive(CustomResource, Serialize, Deserialize, Debug)]
+//! #[kube(group = "example.com", version = "v1", kind = "Foo", namespaced, status = "FooStatus")]
+//! pub struct FooSpec {
+//!     pub replicas: i32,
+//! }
+//!
+//! #[derive(Serialize, Deserialize, Debug)]
+//! pub struct FooStatus {
+//!     pub ready: bool,
+//! }
+//!
+//! let foo = Foo::new("foo", FooSpec { replicas: 1 });
+//! assert_eq!(foo.type_meta.api_version, "example.com/v1");
+//! assert_eq!(Foo::KIND_NAME, "foos");
+//! ```
+//!
+//! Supported attributes:
+//! * `group`, `version` - required, the API group and version of the resource
+//! * `kind` - defaults to the name of the struct without the `Spec` suffix
+//! * `plural` - defaults to the lowercase kind in plural following the English rules, e.g.
+//!   `policies` for `Policy` and `ingresses` for `Ingress`
+//! * `namespaced` - the resource is namespaced, otherwise it is cluster-scoped
+//! * `status` - the status type, enables the status subresource
+//! * `shortname` - a short name of the resource, can be given multiple times
+//!
+//! The generated code uses serde's derives and keeps unknown fields in a `serde_json::Map`, so the
+//! crate using it has to depend on `serde`, `serde_derive` and `serde_json`.
+//!
+//! `#[derive(StructuralSchema)]` implements `StructuralSchema` for structs with named fields,
+//! newtype structs and enums with only unit variants, so `CustomResourceDefinition::for_resource`
+//! can generate the schema of a custom resource. Doc comments become descriptions, and serde's
+//! `rename`, `rename_all`, `default`, `skip` and `skip_serializing_if` attributes are honored.
+extern crate proc_macro;
+
+mod schema;
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{parse_macro_input, DeriveInput, Error, Ident, Lit, LitStr, Meta, NestedMeta};
+
+#[proc_macro_derive(CustomResource, attributes(kube))]
+pub fn derive_custom_resource(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match custom_resource(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+#[proc_macro_derive(StructuralSchema, attributes(serde))]
+pub fn derive_structural_schema(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match schema::structural_schema(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+#[derive(Default)]
+struct KubeAttrs {
+    group: Option<String>,
+    version: Option<String>,
+    kind: Option<LitStr>,
+    plural: Option<String>,
+    namespaced: bool,
+    status: Option<syn::Path>,
+    shortnames: Vec<String>,
+}
+
+fn parse_attrs(input: &DeriveInput) -> syn::Result<KubeAttrs> {
+    let mut attrs = KubeAttrs::default();
+    for attr in input.attrs.iter().filter(|attr| attr.path.is_ident("kube")) {
+        let list = match attr.parse_meta()? {
+            Meta::List(list) => list,
+            meta => return Err(Error::new_spanned(meta, "expected #[kube(...)]")),
+        };
+        for nested in list.nested {
+            match nested {
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("namespaced") => {
+                    attrs.namespaced = true;
+                },
+                NestedMeta::Meta(Meta::NameValue(ref name_value)) => {
+                    let value = match name_value.lit {
+                        Lit::Str(ref value) => value,
+                        ref lit => return Err(Error::new_spanned(lit, "expected a string")),
+                    };
+                    let key = &name_value.path;
+                    if key.is_ident("group") {
+                        attrs.group = Some(value.value());
+                    } else if key.is_ident("version") {
+                        attrs.version = Some(value.value());
+                    } else if key.is_ident("kind") {
+                        attrs.kind = Some(value.clone());
+                    } else if key.is_ident("plural") {
+                        attrs.plural = Some(value.value());
+                    } else if key.is_ident("shortname") {
+                        attrs.shortnames.push(value.value());
+                    } else if key.is_ident("status") {
+                        attrs.status = Some(value.parse()?);
+                    } else {
+                        return Err(Error::new_spanned(key, "unknown kube attribute"));
+                    }
+                },
+                nested => return Err(Error::new_spanned(nested, "unknown kube attribute")),
+            }
+        }
+    }
+    Ok(attrs)
+}
+
+// The plural of a lowercase kind following the English rules
+fn pluralize(singular: &str) -> String {
+    if singular.ends_with('s') || singular.ends_with('x') || singular.ends_with('z')
+        || singular.ends_with("ch") || singular.ends_with("sh") {
+        return format!("{}es", singular);
+    }
+    if let Some(stem) = singular.strip_suffix('y') {
+        if !stem.is_empty() && !stem.ends_with(|c| "aeiou".contains(c)) {
+            return format!("{}ies", stem);
+        }
+    }
+    format!("{}s", singular)
+}
+
+fn custom_resource(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let attrs = parse_attrs(input)?;
+    let spec = &input.ident;
+    let visibility = &input.vis;
+
+    let group = attrs.group.ok_or_else(|| Error::new_spanned(spec, "#[kube(group = \"...\")] is required"))?;
+    let version = attrs.version.ok_or_else(|| Error::new_spanned(spec, "#[kube(version = \"...\")] is required"))?;
+    let kind = match attrs.kind {
+        Some(ref kind) => {
+            // The kind names the generated struct, so it has to be a valid identifier
+            if syn::parse_str::<Ident>(&kind.value()).is_err() {
+                return Err(Error::new_spanned(kind, "the kind must be a valid Rust identifier"));
+            }
+            kind.value()
+        },
+        None => {
+            let name = spec.to_string();
+            match name.strip_suffix("Spec") {
+                Some(kind) if !kind.is_empty() => kind.to_string(),
+                _ => return Err(Error::new_spanned(spec, "#[kube(kind = \"...\")] is required if the struct name doesn't end in `Spec`")),
+            }
+        },
+    };
+    let plural = attrs.plural.unwrap_or_else(|| pluralize(&kind.to_lowercase()));
+    let scope = if attrs.namespaced {
+        quote! { ::kubeclient::api::Namespaced }
+    } else {
+        quote! { ::kubeclient::api::Cluster }
+    };
+    let shortnames = &attrs.shortnames;
+    let api_version = format!("{}/{}", group, version);
+
+    let resource = Ident::new(&kind, Span::call_site());
+    let list = Ident::new(&format!("{}List", kind), Span::call_site());
+
+    let (status_field, status_init, status_type, status_subresource) = match attrs.status {
+        Some(ref status) => (
+            quote! {
+                #[serde(default, skip_serializing_if = "Option::is_none")]
+                pub status: Option<#status>,
+            },
+            quote! { status: None, },
+            quote! { #status },
+            true,
+        ),
+        None => (quote! {}, quote! {}, quote! { () }, false),
+    };
+
+    Ok(quote! {
+        #[derive(::serde_derive::Serialize, ::serde_derive::Deserialize, Debug)]
+        #visibility struct #resource {
+            #[serde(flatten)]
+            pub type_meta: ::kubeclient::apimachinery::apis::meta::v1::TypeMeta,
+            #[serde(default)]
+            pub metadata: ::kubeclient::apimachinery::apis::meta::v1::ObjectMeta,
+            pub spec: #spec,
+            #status_field
+            #[serde(flatten)]
+            pub extra: ::serde_json::Map<String, ::serde_json::Value>,
+        }
+
+        impl #resource {
+            /// Create a new object with the given name and spec
+            pub fn new(name: &str, spec: #spec) -> #resource {
+                #resource {
+                    type_meta: ::kubeclient::apimachinery::apis::meta::v1::TypeMeta {
+                        kind: #kind.to_string(),
+                        api_version: #api_version.to_string(),
+                    },
+                    metadata: ::kubeclient::apimachinery::apis::meta::v1::ObjectMeta {
+                        name: name.to_string(),
+                        ..Default::default()
+                    },
+                    spec,
+                    #status_init
+                    extra: Default::default(),
+                }
+            }
+        }
+
+        impl ::kubeclient::api::KubeKind for #resource {
+            const KIND_NAME: &'static str = #plural;
+            const API_GROUP: &'static str = #group;
+            const API_VERSION: &'static str = #version;
+
+            type List = #list;
+            type Scope = #scope;
+        }
+
+        impl ::kubeclient::api::CustomResource for #resource {
+            const KIND: &'static str = #kind;
+            const SHORT_NAMES: &'static [&'static str] = &[#(#shortnames),*];
+            const STATUS_SUBRESOURCE: bool = #status_subresource;
+
+            type Spec = #spec;
+            type Status = #status_type;
+        }
+
+        #[derive(::serde_derive::Serialize, ::serde_derive::Deserialize, Debug)]
+        #visibility struct #list {
+            #[serde(flatten)]
+            pub type_meta: ::kubeclient::apimachinery::apis::meta::v1::TypeMeta,
+            pub items: Vec<#resource>,
+            pub metadata: ::kubeclient::apimachinery::apis::meta::v1::ListMeta,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{custom_resource, pluralize};
+
+    #[test]
+    fn plurals() {
+        assert_eq!(pluralize("foo"), "foos");
+        assert_eq!(pluralize("policy"), "policies");
+        assert_eq!(pluralize("gateway"), "gateways");
+        assert_eq!(pluralize("ingress"), "ingresses");
+        assert_eq!(pluralize("proxy"), "proxies");
+        assert_eq!(pluralize("box"), "boxes");
+        assert_eq!(pluralize("patch"), "patches");
+    }
+
+    #[test]
+    fn invalid_kind_is_an_error() {
+        let input = syn::parse_str(r#"
+            #[kube(group = "example.com", version = "v1", kind = "not valid")]
+            struct FooSpec {}
+        "#).unwrap();
+        let error = custom_resource(&input).unwrap_err();
+        assert_eq!(error.to_string(), "the kind must be a valid Rust identifier");
+    }
+}
@@ -0,0 +1,226 @@
+//! `#[derive(StructuralSchema)]`
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Attribute, Data, DeriveInput, Error, Fields, Lit, Meta, NestedMeta};
+
+#[derive(Default)]
+struct SerdeAttrs {
+    rename: Option<String>,
+    rename_all: Option<String>,
+    default: bool,
+    skip: bool,
+    skip_serializing_if: bool,
+    flatten: bool,
+}
+
+fn serde_attrs(attrs: &[Attribute]) -> syn::Result<SerdeAttrs> {
+    let mut serde = SerdeAttrs::default();
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("serde")) {
+        let list = match attr.parse_meta()? {
+            Meta::List(list) => list,
+            meta => return Err(Error::new_spanned(meta, "expected #[serde(...)]")),
+        };
+        for nested in list.nested {
+            match nested {
+                NestedMeta::Meta(Meta::Path(ref path)) => {
+                    if path.is_ident("default") {
+                        serde.default = true;
+                    } else if path.is_ident("skip") || path.is_ident("skip_serializing") {
+                        serde.skip = true;
+                    } else if path.is_ident("flatten") {
+                        serde.flatten = true;
+                    }
+                },
+                NestedMeta::Meta(Meta::NameValue(ref name_value)) => {
+                    let value = match name_value.lit {
+                        Lit::Str(ref value) => value.value(),
+                        _ => continue,
+                    };
+                    let key = &name_value.path;
+                    if key.is_ident("rename") {
+                        serde.rename = Some(value);
+                    } else if key.is_ident("rename_all") {
+                        serde.rename_all = Some(value);
+                    } else if key.is_ident("default") {
+                        serde.default = true;
+                    } else if key.is_ident("skip_serializing_if") {
+                        serde.skip_serializing_if = true;
+                    }
+                },
+                // Other attributes, e.g. `rename(serialize = "...")`, don't affect the schema
+                _ => (),
+            }
+        }
+    }
+    Ok(serde)
+}
+
+// The doc comments of an item, joined into a description
+fn description(attrs: &[Attribute]) -> Option<String> {
+    let lines: Vec<String> = attrs.iter()
+        .filter(|attr| attr.path.is_ident("doc"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::NameValue(ref name_value)) => match name_value.lit {
+                Lit::Str(ref value) => Some(value.value().trim().to_string()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+    if lines.is_empty() {
+        None
+    } else {
+        Some(lines.join("\n"))
+    }
+}
+
+fn split_words(name: &str, snake_case: bool) -> Vec<String> {
+    if snake_case {
+        return name.split('_').filter(|word| !word.is_empty()).map(str::to_lowercase).collect();
+    }
+    let mut words = Vec::new();
+    let mut word = String::new();
+    for c in name.chars() {
+        if c.is_uppercase() && !word.is_empty() {
+            words.push(word.to_lowercase());
+            word = String::new();
+        }
+        word.push(c);
+    }
+    if !word.is_empty() {
+        words.push(word.to_lowercase());
+    }
+    words
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+// Apply a serde `rename_all` rule. Fields are named in snake_case, variants in PascalCase.
+fn rename(name: &str, rule: &str, snake_case: bool) -> syn::Result<String> {
+    let name = name.trim_start_matches("r#");
+    let words = split_words(name, snake_case);
+    Ok(match rule {
+        "lowercase" => words.concat(),
+        "UPPERCASE" => words.concat().to_uppercase(),
+        "PascalCase" => words.iter().map(|word| capitalize(word)).collect(),
+        "camelCase" => words.iter().enumerate()
+            .map(|(i, word)| if i == 0 { word.clone() } else { capitalize(word) })
+            .collect(),
+        "snake_case" => words.join("_"),
+        "SCREAMING_SNAKE_CASE" => words.join("_").to_uppercase(),
+        "kebab-case" => words.join("-"),
+        "SCREAMING-KEBAB-CASE" => words.join("-").to_uppercase(),
+        _ => return Err(Error::new(proc_macro2::Span::call_site(), format!("unknown rename_all rule `{}`", rule))),
+    })
+}
+
+fn serialized_name(ident: &syn::Ident, attrs: &SerdeAttrs, rule: Option<&String>, snake_case: bool) -> syn::Result<String> {
+    match (&attrs.rename, rule) {
+        (Some(name), _) => Ok(name.clone()),
+        (None, Some(rule)) => rename(&ident.to_string(), rule, snake_case),
+        (None, None) => Ok(ident.to_string().trim_start_matches("r#").to_string()),
+    }
+}
+
+fn description_tokens(description: Option<String>) -> TokenStream {
+    match description {
+        Some(description) => quote! { Some(#description.to_string()) },
+        None => quote! { None },
+    }
+}
+
+pub fn structural_schema(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    let container = serde_attrs(&input.attrs)?;
+    let container_description = description_tokens(description(&input.attrs));
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+
+    let body = match input.data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(ref fields) => {
+                let mut properties = Vec::new();
+                for field in &fields.named {
+                    let attrs = serde_attrs(&field.attrs)?;
+                    if attrs.skip {
+                        continue;
+                    }
+                    if attrs.flatten {
+                        return Err(Error::new_spanned(field, "#[serde(flatten)] is not supported by StructuralSchema"));
+                    }
+                    let ident = field.ident.as_ref().expect("named field");
+                    let property = serialized_name(ident, &attrs, container.rename_all.as_ref(), true)?;
+                    let ty = &field.ty;
+                    let field_description = description_tokens(description(&field.attrs));
+                    let can_be_omitted = container.default || attrs.default || attrs.skip_serializing_if;
+                    properties.push(quote! {
+                        let mut schema = <#ty as ::kubeclient::api::apiextensions::v1::StructuralSchema>::schema();
+                        if schema.description.is_none() {
+                            schema.description = #field_description;
+                        }
+                        properties.insert(#property.to_string(), schema);
+                        if !#can_be_omitted && !<#ty as ::kubeclient::api::apiextensions::v1::StructuralSchema>::optional() {
+                            required.push(#property.to_string());
+                        }
+                    });
+                }
+                quote! {
+                    let mut properties = ::std::collections::BTreeMap::new();
+                    let mut required = Vec::new();
+                    #(#properties)*
+                    ::kubeclient::api::apiextensions::v1::JSONSchemaProps {
+                        type_: Some("object".to_string()),
+                        description: #container_description,
+                        properties,
+                        required,
+                        ..Default::default()
+                    }
+                }
+            },
+            Fields::Unnamed(ref fields) if fields.unnamed.len() == 1 => {
+                let ty = &fields.unnamed[0].ty;
+                quote! {
+                    let mut schema = <#ty as ::kubeclient::api::apiextensions::v1::StructuralSchema>::schema();
+                    if schema.description.is_none() {
+                        schema.description = #container_description;
+                    }
+                    schema
+                }
+            },
+            _ => return Err(Error::new_spanned(input, "StructuralSchema needs named fields or a newtype struct")),
+        },
+        Data::Enum(ref data) => {
+            let mut values = Vec::new();
+            for variant in &data.variants {
+                if !variant.fields.is_empty() {
+                    return Err(Error::new_spanned(variant, "StructuralSchema only supports enums with unit variants"));
+                }
+                let attrs = serde_attrs(&variant.attrs)?;
+                if attrs.skip {
+                    continue;
+                }
+                values.push(serialized_name(&variant.ident, &attrs, container.rename_all.as_ref(), false)?);
+            }
+            quote! {
+                ::kubeclient::api::apiextensions::v1::JSONSchemaProps {
+                    description: #container_description,
+                    ..::kubeclient::api::apiextensions::v1::JSONSchemaProps::string_enum(&[#(#values),*])
+                }
+            }
+        },
+        Data::Union(_) => return Err(Error::new_spanned(input, "StructuralSchema can't be derived for unions")),
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::kubeclient::api::apiextensions::v1::StructuralSchema for #name #type_generics #where_clause {
+            fn schema() -> ::kubeclient::api::apiextensions::v1::JSONSchemaProps {
+                #body
+            }
+        }
+    })
+}
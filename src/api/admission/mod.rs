@@ -1,3 +1,27 @@
+use base64;
+use serde_json;
+
+use crate::json_patch::Patch;
+
 pub mod v1beta1;
+pub mod v1;
 
 pub use self::v1beta1::*;
+
+pub const API_GROUP: &str = "admission.k8s.io";
+
+/// Kind of the objects sent to and returned by admission webhooks
+pub const ADMISSION_REVIEW_KIND: &str = "AdmissionReview";
+
+/// Patch type of JSON Patches, the only type supported at the moment
+pub const PATCH_TYPE_JSON_PATCH: &str = "JSONPatch";
+
+// The base64 encoded patch and patch type of an `AdmissionResponse`, both empty for an empty patch
+fn encode_patch(patch: &Patch) -> (String, Option<String>) {
+    if patch.is_empty() {
+        (String::new(), None)
+    } else {
+        let json = serde_json::to_vec(patch).expect("JSON Patches always serialize");
+        (base64::encode(&json), Some(PATCH_TYPE_JSON_PATCH.to_string()))
+    }
+}
@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+
+use serde_json::{self, Value};
+use uuid::Uuid;
+
+use crate::apimachinery::apis::meta;
+use crate::api;
+use crate::json_patch::Patch;
+
+use super::{v1beta1, API_GROUP, ADMISSION_REVIEW_KIND};
+pub const API_VERSION: &str = "v1";
+
+
+type Operation = String;
+
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AdmissionReview<T> {
+    #[serde(flatten)]
+    pub type_meta: meta::v1::TypeMeta,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request: Option<AdmissionRequest<T>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response: Option<AdmissionResponse>,
+}
+
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AdmissionRequest<T> {
+    pub uid: Uuid,
+    pub kind: meta::v1::GroupVersionKind,
+    pub resource: meta::v1::GroupVersionResource,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub sub_resource: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_kind: Option<meta::v1::GroupVersionKind>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_resource: Option<meta::v1::GroupVersionResource>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_sub_resource: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub name: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub namespace: String,
+    pub operation: Operation,
+    pub user_info: api::authentication::v1::UserInfo,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub object: Option<T>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old_object: Option<T>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dry_run: Option<bool>,
+    // The options of the operation, e.g. `CreateOptions`, which are not modelled yet
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub options: Option<Value>,
+}
+
+type PatchType = String;
+
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AdmissionResponse {
+    pub uid: Uuid,
+    pub allowed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<meta::v1::Status>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub patch: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub patch_type: Option<PatchType>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub audit_annotations: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+impl AdmissionResponse {
+    /// Set the patch of the response, e.g. one produced by `json_patch::diff` from the request's
+    /// object and its mutated copy. An empty patch clears the patch from the response.
+    pub fn set_patch(&mut self, patch: &Patch) {
+        let (encoded, patch_type) = super::encode_patch(patch);
+        self.patch = encoded;
+        self.patch_type = patch_type;
+    }
+}
+
+// Conversion between v1beta1 and v1. The two versions are identical apart from the UIDs being
+// optional in v1beta1, a missing UID is converted to the nil UUID.
+
+fn review_type_meta(api_version: &str) -> meta::v1::TypeMeta {
+    meta::v1::TypeMeta {
+        kind: ADMISSION_REVIEW_KIND.to_string(),
+        api_version: format!("{}/{}", API_GROUP, api_version),
+    }
+}
+
+impl<T> From<v1beta1::AdmissionReview<T>> for AdmissionReview<T> {
+    fn from(review: v1beta1::AdmissionReview<T>) -> Self {
+        AdmissionReview {
+            type_meta: review_type_meta(API_VERSION),
+            request: review.request.map(From::from),
+            response: review.response.map(From::from),
+        }
+    }
+}
+
+impl<T> From<AdmissionReview<T>> for v1beta1::AdmissionReview<T> {
+    fn from(review: AdmissionReview<T>) -> Self {
+        v1beta1::AdmissionReview {
+            type_meta: review_type_meta(v1beta1::API_VERSION),
+            request: review.request.map(From::from),
+            response: review.response.map(From::from),
+        }
+    }
+}
+
+impl<T> From<v1beta1::AdmissionRequest<T>> for AdmissionRequest<T> {
+    fn from(request: v1beta1::AdmissionRequest<T>) -> Self {
+        AdmissionRequest {
+            uid: request.uid.unwrap_or_else(Uuid::nil),
+            kind: request.kind,
+            resource: request.resource,
+            sub_resource: request.subresource,
+            request_kind: request.request_kind,
+            request_resource: request.request_resource,
+            request_sub_resource: request.request_sub_resource,
+            name: request.name,
+            namespace: request.namespace,
+            operation: request.operation,
+            user_info: request.user_info,
+            object: request.object,
+            old_object: request.old_object,
+            dry_run: request.dry_run,
+            options: request.options,
+        }
+    }
+}
+
+impl<T> From<AdmissionRequest<T>> for v1beta1::AdmissionRequest<T> {
+    fn from(request: AdmissionRequest<T>) -> Self {
+        v1beta1::AdmissionRequest {
+            uid: Some(request.uid),
+            kind: request.kind,
+            resource: request.resource,
+            subresource: request.sub_resource,
+            request_kind: request.request_kind,
+            request_resource: request.request_resource,
+            request_sub_resource: request.request_sub_resource,
+            name: request.name,
+            namespace: request.namespace,
+            operation: request.operation,
+            user_info: request.user_info,
+            object: request.object,
+            old_object: request.old_object,
+            dry_run: request.dry_run,
+            options: request.options,
+        }
+    }
+}
+
+impl From<v1beta1::AdmissionResponse> for AdmissionResponse {
+    fn from(response: v1beta1::AdmissionResponse) -> Self {
+        AdmissionResponse {
+            uid: response.uid.unwrap_or_else(Uuid::nil),
+            allowed: response.allowed,
+            status: response.status,
+            patch: response.patch,
+            patch_type: response.patch_type,
+            audit_annotations: response.audit_annotations,
+            warnings: response.warnings,
+        }
+    }
+}
+
+impl From<AdmissionResponse> for v1beta1::AdmissionResponse {
+    fn from(response: AdmissionResponse) -> Self {
+        v1beta1::AdmissionResponse {
+            uid: Some(response.uid),
+            allowed: response.allowed,
+            status: response.status,
+            patch: response.patch,
+            patch_type: response.patch_type,
+            audit_annotations: response.audit_annotations,
+            warnings: response.warnings,
+        }
+    }
+}
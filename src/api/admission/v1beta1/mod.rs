@@ -1,8 +1,13 @@
+use std::collections::HashMap;
+
+use serde_json::{self, Value};
 use uuid::Uuid;
 
 use crate::apimachinery::apis::meta;
 use crate::api;
+use crate::json_patch::Patch;
 
+pub const API_VERSION: &str = "v1beta1";
 
 type Operation = String;
 
@@ -24,8 +29,14 @@ pub struct AdmissionRequest<T> {
     pub uid: Option<Uuid>,
     pub kind: meta::v1::GroupVersionKind,
     pub resource: meta::v1::GroupVersionResource,
-    #[serde(default, skip_serializing_if = "String::is_empty")]
+    #[serde(rename = "subResource", default, skip_serializing_if = "String::is_empty")]
     pub subresource: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_kind: Option<meta::v1::GroupVersionKind>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_resource: Option<meta::v1::GroupVersionResource>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub request_sub_resource: String,
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub name: String,
     #[serde(default, skip_serializing_if = "String::is_empty")]
@@ -36,10 +47,17 @@ pub struct AdmissionRequest<T> {
     pub object: Option<T>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub old_object: Option<T>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dry_run: Option<bool>,
+    // The options of the operation, e.g. `CreateOptions`, which are not modelled yet
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub options: Option<Value>,
 }
 
 type PatchType = String;
 
+pub use super::PATCH_TYPE_JSON_PATCH;
+
 #[serde(rename_all = "camelCase")]
 #[derive(Serialize, Deserialize, Debug)]
 pub struct AdmissionResponse {
@@ -48,8 +66,22 @@ pub struct AdmissionResponse {
     pub allowed: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub status: Option<meta::v1::Status>,
-    #[serde(skip_serializing_if = "String::is_empty")]
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub patch: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub patch_type: Option<PatchType>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub audit_annotations: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+impl AdmissionResponse {
+    /// Set the patch of the response, e.g. one produced by `json_patch::diff` from the request's
+    /// object and its mutated copy. An empty patch clears the patch from the response.
+    pub fn set_patch(&mut self, patch: &Patch) {
+        let (encoded, patch_type) = super::encode_patch(patch);
+        self.patch = encoded;
+        self.patch_type = patch_type;
+    }
 }
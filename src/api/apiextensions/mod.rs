@@ -0,0 +1,5 @@
+pub mod v1;
+
+pub use self::v1::*;
+
+pub const API_GROUP: &str = "apiextensions.k8s.io";
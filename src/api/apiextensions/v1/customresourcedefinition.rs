@@ -0,0 +1,351 @@
+use std::collections::BTreeMap;
+
+use serde::Serializer;
+use serde_json::Value;
+
+use crate::api::core::v1::SecretData;
+use crate::apimachinery::apis::meta;
+use super::{API_GROUP, API_VERSION};
+
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct CustomResourceDefinition {
+    #[serde(flatten)]
+    pub type_meta: meta::v1::TypeMeta,
+    #[serde(default)]
+    pub metadata: meta::v1::ObjectMeta,
+    pub spec: CustomResourceDefinitionSpec,
+    #[serde(default)]
+    pub status: CustomResourceDefinitionStatus,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+kube_kind!(CustomResourceDefinition, CustomResourceDefinitionList, "customresourcedefinitions", Cluster);
+
+pub type ResourceScope = String;
+
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct CustomResourceDefinitionSpec {
+    pub group: String,
+    pub names: CustomResourceDefinitionNames,
+    pub scope: ResourceScope,
+    pub versions: Vec<CustomResourceDefinitionVersion>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conversion: Option<CustomResourceConversion>,
+    #[serde(default, skip_serializing_if = "::std::ops::Not::not")]
+    pub preserve_unknown_fields: bool,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct CustomResourceDefinitionNames {
+    pub plural: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub singular: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub short_names: Vec<String>,
+    pub kind: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub list_kind: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub categories: Vec<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct CustomResourceDefinitionVersion {
+    pub name: String,
+    pub served: bool,
+    pub storage: bool,
+    #[serde(default, skip_serializing_if = "::std::ops::Not::not")]
+    pub deprecated: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deprecation_warning: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<CustomResourceValidation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subresources: Option<CustomResourceSubresources>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub additional_printer_columns: Vec<CustomResourceColumnDefinition>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct CustomResourceValidation {
+    #[serde(rename = "openAPIV3Schema", default, skip_serializing_if = "Option::is_none")]
+    pub open_api_v3_schema: Option<JSONSchemaProps>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct CustomResourceSubresources {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<CustomResourceSubresourceStatus>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scale: Option<CustomResourceSubresourceScale>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct CustomResourceSubresourceStatus {}
+
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct CustomResourceSubresourceScale {
+    pub spec_replicas_path: String,
+    pub status_replicas_path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label_selector_path: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct CustomResourceColumnDefinition {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub column_type: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub format: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<i32>,
+    pub json_path: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+pub type ConversionStrategyType = String;
+
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct CustomResourceConversion {
+    pub strategy: ConversionStrategyType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook: Option<WebhookConversion>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct WebhookConversion {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_config: Option<WebhookClientConfig>,
+    pub conversion_review_versions: Vec<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct WebhookClientConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub service: Option<ServiceReference>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ca_bundle: Option<SecretData>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct ServiceReference {
+    pub namespace: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub port: Option<i32>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct CustomResourceDefinitionStatus {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub conditions: Vec<CustomResourceDefinitionCondition>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accepted_names: Option<CustomResourceDefinitionNames>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stored_versions: Vec<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+pub type CustomResourceDefinitionConditionType = String;
+
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct CustomResourceDefinitionCondition {
+    #[serde(rename = "type")]
+    pub condition_type: CustomResourceDefinitionConditionType,
+    pub status: crate::api::core::v1::ConditionStatus,
+    #[serde(default)]
+    pub last_transition_time: Option<meta::v1::Time>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub reason: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub message: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl CustomResourceDefinition {
+    /// Whether the API server has started serving the custom resource
+    pub fn is_established(&self) -> bool {
+        self.status.conditions.iter()
+            .any(|condition| condition.condition_type == "Established" && condition.status == "True")
+    }
+}
+
+/// An OpenAPI v3 schema, as used for the validation of custom resources
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct JSONSchemaProps {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(rename = "$schema", default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+    #[serde(rename = "$ref", default, skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub type_: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none", serialize_with = "serialize_number")]
+    pub maximum: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exclusive_maximum: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none", serialize_with = "serialize_number")]
+    pub minimum: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exclusive_minimum: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_length: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_items: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_items: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unique_items: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none", serialize_with = "serialize_number")]
+    pub multiple_of: Option<f64>,
+    #[serde(rename = "enum", default, skip_serializing_if = "Vec::is_empty")]
+    pub enum_: Vec<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_properties: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_properties: Option<i64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub required: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub items: Option<Box<JSONSchemaPropsOrArray>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub all_of: Vec<JSONSchemaProps>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub one_of: Vec<JSONSchemaProps>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub any_of: Vec<JSONSchemaProps>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub not: Option<Box<JSONSchemaProps>>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub properties: BTreeMap<String, JSONSchemaProps>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub additional_properties: Option<Box<JSONSchemaPropsOrBool>>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub pattern_properties: BTreeMap<String, JSONSchemaProps>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub dependencies: BTreeMap<String, JSONSchemaPropsOrStringArray>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub additional_items: Option<Box<JSONSchemaPropsOrBool>>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub definitions: BTreeMap<String, JSONSchemaProps>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_docs: Option<ExternalDocumentation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub example: Option<Value>,
+    #[serde(default, skip_serializing_if = "::std::ops::Not::not")]
+    pub nullable: bool,
+    #[serde(rename = "x-kubernetes-preserve-unknown-fields", default, skip_serializing_if = "Option::is_none")]
+    pub x_kubernetes_preserve_unknown_fields: Option<bool>,
+    #[serde(rename = "x-kubernetes-embedded-resource", default, skip_serializing_if = "::std::ops::Not::not")]
+    pub x_kubernetes_embedded_resource: bool,
+    #[serde(rename = "x-kubernetes-int-or-string", default, skip_serializing_if = "::std::ops::Not::not")]
+    pub x_kubernetes_int_or_string: bool,
+    #[serde(rename = "x-kubernetes-list-map-keys", default, skip_serializing_if = "Vec::is_empty")]
+    pub x_kubernetes_list_map_keys: Vec<String>,
+    #[serde(rename = "x-kubernetes-list-type", default, skip_serializing_if = "Option::is_none")]
+    pub x_kubernetes_list_type: Option<String>,
+    #[serde(rename = "x-kubernetes-map-type", default, skip_serializing_if = "Option::is_none")]
+    pub x_kubernetes_map_type: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+// Go writes floats without a fractional part like integers, e.g. `10` instead of `10.0`
+fn serialize_number<S: Serializer>(value: &Option<f64>, serializer: S) -> Result<S::Ok, S::Error> {
+    match *value {
+        Some(number) if number.fract() == 0.0 && number.abs() < 1e18 => serializer.serialize_i64(number as i64),
+        Some(number) => serializer.serialize_f64(number),
+        None => serializer.serialize_none(),
+    }
+}
+
+#[serde(untagged)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum JSONSchemaPropsOrArray {
+    Schema(JSONSchemaProps),
+    Schemas(Vec<JSONSchemaProps>),
+}
+
+#[serde(untagged)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum JSONSchemaPropsOrBool {
+    Bool(bool),
+    Schema(JSONSchemaProps),
+}
+
+#[serde(untagged)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum JSONSchemaPropsOrStringArray {
+    Property(Vec<String>),
+    Schema(JSONSchemaProps),
+}
+
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct ExternalDocumentation {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
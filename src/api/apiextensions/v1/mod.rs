@@ -0,0 +1,8 @@
+mod customresourcedefinition;
+mod schema;
+
+pub use self::customresourcedefinition::*;
+pub use self::schema::*;
+
+use super::API_GROUP;
+pub const API_VERSION: &str = "v1";
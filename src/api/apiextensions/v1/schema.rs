@@ -0,0 +1,232 @@
+//! Generation of structural schemas for Rust types, used to produce the CRDs of custom resources.
+use std::collections::{BTreeMap, HashMap};
+
+use serde_json::Value;
+
+use crate::api::{CustomResource, KubeKind, Scope};
+use crate::apimachinery::api::resource::Quantity;
+use crate::apimachinery::apis::meta;
+use crate::apimachinery::util::IntOrString;
+use super::*;
+
+/// Types that can describe themselves with a structural OpenAPI v3 schema.
+/// Structs and enums can implement this with `#[derive(StructuralSchema)]` from the `derive`
+/// feature.
+pub trait StructuralSchema {
+    fn schema() -> JSONSchemaProps;
+
+    /// Whether a field of this type can be left out of an object, i.e. it's not required
+    fn optional() -> bool {
+        false
+    }
+}
+
+fn typed_schema(type_: &str, format: Option<&str>) -> JSONSchemaProps {
+    JSONSchemaProps {
+        type_: Some(type_.to_string()),
+        format: format.map(str::to_string),
+        ..Default::default()
+    }
+}
+
+macro_rules! structural_schema {
+    ( $typ:ty, $type_:expr, $format:expr ) => {
+        impl StructuralSchema for $typ {
+            fn schema() -> JSONSchemaProps {
+                typed_schema($type_, $format)
+            }
+        }
+    }
+}
+
+structural_schema!(bool, "boolean", None);
+structural_schema!(i8, "integer", Some("int32"));
+structural_schema!(i16, "integer", Some("int32"));
+structural_schema!(i32, "integer", Some("int32"));
+structural_schema!(i64, "integer", Some("int64"));
+structural_schema!(u8, "integer", Some("int32"));
+structural_schema!(u16, "integer", Some("int32"));
+structural_schema!(u32, "integer", Some("int64"));
+structural_schema!(u64, "integer", Some("int64"));
+structural_schema!(f32, "number", Some("float"));
+structural_schema!(f64, "number", Some("double"));
+structural_schema!(String, "string", None);
+structural_schema!(meta::v1::Time, "string", Some("date-time"));
+
+impl<T: StructuralSchema> StructuralSchema for Option<T> {
+    fn schema() -> JSONSchemaProps {
+        JSONSchemaProps {
+            nullable: true,
+            ..T::schema()
+        }
+    }
+
+    fn optional() -> bool {
+        true
+    }
+}
+
+impl<T: StructuralSchema> StructuralSchema for Box<T> {
+    fn schema() -> JSONSchemaProps {
+        T::schema()
+    }
+
+    fn optional() -> bool {
+        T::optional()
+    }
+}
+
+impl<T: StructuralSchema> StructuralSchema for Vec<T> {
+    fn schema() -> JSONSchemaProps {
+        JSONSchemaProps {
+            items: Some(Box::new(JSONSchemaPropsOrArray::Schema(T::schema()))),
+            ..typed_schema("array", None)
+        }
+    }
+}
+
+fn map_schema(values: JSONSchemaProps) -> JSONSchemaProps {
+    JSONSchemaProps {
+        additional_properties: Some(Box::new(JSONSchemaPropsOrBool::Schema(values))),
+        ..typed_schema("object", None)
+    }
+}
+
+impl<T: StructuralSchema> StructuralSchema for HashMap<String, T> {
+    fn schema() -> JSONSchemaProps {
+        map_schema(T::schema())
+    }
+}
+
+impl<T: StructuralSchema> StructuralSchema for BTreeMap<String, T> {
+    fn schema() -> JSONSchemaProps {
+        map_schema(T::schema())
+    }
+}
+
+// Arbitrary JSON is kept as-is by the API server
+fn preserve_unknown_fields() -> JSONSchemaProps {
+    JSONSchemaProps {
+        x_kubernetes_preserve_unknown_fields: Some(true),
+        ..Default::default()
+    }
+}
+
+impl StructuralSchema for Value {
+    fn schema() -> JSONSchemaProps {
+        preserve_unknown_fields()
+    }
+}
+
+// Used as the status of custom resources without a status
+impl StructuralSchema for () {
+    fn schema() -> JSONSchemaProps {
+        JSONSchemaProps {
+            x_kubernetes_preserve_unknown_fields: Some(true),
+            ..typed_schema("object", None)
+        }
+    }
+
+    fn optional() -> bool {
+        true
+    }
+}
+
+impl JSONSchemaProps {
+    /// A string schema that only allows the given values
+    pub fn string_enum(values: &[&str]) -> JSONSchemaProps {
+        JSONSchemaProps {
+            enum_: values.iter().map(|value| Value::String(value.to_string())).collect(),
+            ..typed_schema("string", None)
+        }
+    }
+}
+
+fn int_or_string() -> JSONSchemaProps {
+    JSONSchemaProps {
+        x_kubernetes_int_or_string: true,
+        any_of: vec![typed_schema("integer", None), typed_schema("string", None)],
+        ..Default::default()
+    }
+}
+
+impl StructuralSchema for IntOrString {
+    fn schema() -> JSONSchemaProps {
+        int_or_string()
+    }
+}
+
+impl StructuralSchema for Quantity {
+    fn schema() -> JSONSchemaProps {
+        int_or_string()
+    }
+}
+
+impl CustomResourceDefinition {
+    /// Generate the definition of a custom resource, with a single served and stored version
+    pub fn for_resource<T>() -> CustomResourceDefinition
+        where T: CustomResource,
+              T::Spec: StructuralSchema,
+              T::Status: StructuralSchema,
+    {
+        let mut properties = BTreeMap::new();
+        properties.insert("spec".to_string(), T::Spec::schema());
+        if T::STATUS_SUBRESOURCE {
+            properties.insert("status".to_string(), T::Status::schema());
+        }
+        let schema = JSONSchemaProps {
+            properties,
+            required: vec!["spec".to_string()],
+            ..typed_schema("object", None)
+        };
+        let subresources = if T::STATUS_SUBRESOURCE {
+            Some(CustomResourceSubresources {
+                status: Some(CustomResourceSubresourceStatus::default()),
+                scale: None,
+                extra: Default::default(),
+            })
+        } else {
+            None
+        };
+
+        CustomResourceDefinition {
+            type_meta: meta::v1::TypeMeta {
+                kind: "CustomResourceDefinition".to_string(),
+                api_version: format!("{}/{}", API_GROUP, API_VERSION),
+            },
+            metadata: meta::v1::ObjectMeta {
+                name: format!("{}.{}", T::KIND_NAME, T::API_GROUP),
+                ..Default::default()
+            },
+            spec: CustomResourceDefinitionSpec {
+                group: T::API_GROUP.to_string(),
+                names: CustomResourceDefinitionNames {
+                    plural: T::KIND_NAME.to_string(),
+                    singular: T::KIND.to_lowercase(),
+                    short_names: T::SHORT_NAMES.iter().map(|name| name.to_string()).collect(),
+                    kind: T::KIND.to_string(),
+                    list_kind: format!("{}List", T::KIND),
+                    categories: Vec::new(),
+                    extra: Default::default(),
+                },
+                scope: if <<T as KubeKind>::Scope as Scope>::NAMESPACED { "Namespaced" } else { "Cluster" }.to_string(),
+                versions: vec![CustomResourceDefinitionVersion {
+                    name: T::API_VERSION.to_string(),
+                    served: true,
+                    storage: true,
+                    schema: Some(CustomResourceValidation {
+                        open_api_v3_schema: Some(schema),
+                        extra: Default::default(),
+                    }),
+                    subresources,
+                    ..Default::default()
+                }],
+                conversion: None,
+                preserve_unknown_fields: false,
+                extra: Default::default(),
+            },
+            status: Default::default(),
+            extra: Default::default(),
+        }
+    }
+}
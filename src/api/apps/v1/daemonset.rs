@@ -16,9 +16,11 @@ pub struct DaemonSet {
     pub spec: DaemonSetSpec,
     #[serde(default)]
     pub status: DaemonSetStatus,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-kube_kind!(DaemonSet, DaemonSetList, "daemonsets");
+kube_kind!(DaemonSet, DaemonSetList, "daemonsets", Namespaced);
 
 #[serde(rename_all = "camelCase")]
 #[derive(Default, Serialize, Deserialize, Debug)]
@@ -32,6 +34,8 @@ pub struct DaemonSetSpec {
     pub min_ready_seconds: i32,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub revision_history_limit: Option<i32>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 pub type DaemonSetUpdateStrategyType = String;
@@ -43,6 +47,8 @@ pub struct DaemonSetUpdateStrategy {
     pub strategy_type: DaemonSetUpdateStrategyType,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub rolling_update: Option<RollingUpdateDaemonSet>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -50,6 +56,8 @@ pub struct DaemonSetUpdateStrategy {
 pub struct RollingUpdateDaemonSet {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub max_unavailable: Option<IntOrString>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -71,6 +79,8 @@ pub struct DaemonSetStatus {
     pub collision_count: Option<i32>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub conditions: Vec<DaemonSetCondition>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 type DaemonSetConditionType = String;
@@ -81,10 +91,12 @@ pub struct DaemonSetCondition {
     #[serde(rename = "type")]
     pub condition_type: DaemonSetConditionType,
     pub status: api::core::v1::ConditionStatus,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
     pub last_transition_time: Option<meta::v1::Time>,
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub reason: String,
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub message: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
@@ -16,9 +16,11 @@ pub struct Deployment {
     pub spec: DeploymentSpec,
     #[serde(default)]
     pub status: DeploymentStatus,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-kube_kind!(Deployment, DeploymentList, "deployments");
+kube_kind!(Deployment, DeploymentList, "deployments", Namespaced);
 
 #[serde(rename_all = "camelCase")]
 #[derive(Default, Serialize, Deserialize, Debug)]
@@ -37,6 +39,8 @@ pub struct DeploymentSpec {
     pub paused: bool,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub progress_deadline_seconds: Option<i32>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 pub type DeploymentStrategyType = String;
@@ -48,6 +52,8 @@ pub struct DeploymentStrategy {
     pub strategy_type: DeploymentStrategyType,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub rolling_update: Option<RollingUpdateDeployment>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -57,6 +63,8 @@ pub struct RollingUpdateDeployment {
     pub max_unavailable: Option<IntOrString>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub max_surge: Option<IntOrString>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -78,6 +86,8 @@ pub struct DeploymentStatus {
     pub collision_count: Option<i32>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub conditions: Vec<DeploymentCondition>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 type DeploymentConditionType = String;
@@ -88,12 +98,14 @@ pub struct DeploymentCondition {
     #[serde(rename = "type")]
     pub condition_type: DeploymentConditionType,
     pub status: api::core::v1::ConditionStatus,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
     pub last_transition_time: Option<meta::v1::Time>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
     pub last_update_time: Option<meta::v1::Time>,
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub reason: String,
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub message: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
@@ -15,9 +15,11 @@ pub struct ReplicaSet {
     pub spec: ReplicaSetSpec,
     #[serde(default)]
     pub status: ReplicaSetStatus,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-kube_kind!(ReplicaSet, ReplicaSetList, "replicasets");
+kube_kind!(ReplicaSet, ReplicaSetList, "replicasets", Namespaced);
 
 #[serde(rename_all = "camelCase")]
 #[derive(Default, Serialize, Deserialize, Debug)]
@@ -29,6 +31,8 @@ pub struct ReplicaSetSpec {
     pub template: api::core::v1::PodTemplateSpec,
     #[serde(default, skip_serializing_if = "Zero::is_zero")]
     pub min_ready_seconds: i32,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -45,6 +49,8 @@ pub struct ReplicaSetStatus {
     pub observed_generation: i64,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub conditions: Vec<ReplicaSetCondition>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 pub type ReplicaSetConditionType = String;
@@ -55,10 +61,12 @@ pub struct ReplicaSetCondition {
     #[serde(rename = "type")]
     pub condition_type: ReplicaSetConditionType,
     pub status: api::core::v1::ConditionStatus,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
     pub last_transition_time: Option<meta::v1::Time>,
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub reason: String,
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub message: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
@@ -15,9 +15,11 @@ pub struct StatefulSet {
     pub spec: StatefulSetSpec,
     #[serde(default)]
     pub status: StatefulSetStatus,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-kube_kind!(StatefulSet, StatefulSetList, "statefulsets");
+kube_kind!(StatefulSet, StatefulSetList, "statefulsets", Namespaced);
 
 pub type PodManagementPolicyType = String;
 
@@ -37,6 +39,8 @@ pub struct StatefulSetSpec {
     pub update_strategy: StatefulSetUpdateStrategy,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub revision_history_limit: Option<i32>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 pub type StatefulSetUpdateStrategyType = String;
@@ -48,6 +52,8 @@ pub struct StatefulSetUpdateStrategy {
     pub strategy_type: StatefulSetUpdateStrategyType,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub rolling_update: Option<RollingUpdateStatefulSetStrategy>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 impl ::std::default::Default for StatefulSetUpdateStrategy {
@@ -55,6 +61,7 @@ impl ::std::default::Default for StatefulSetUpdateStrategy {
         StatefulSetUpdateStrategy {
             strategy_type: "RollingUpdate".to_string(),
             rolling_update: None,
+            extra: Default::default(),
         }
     }
 }
@@ -64,6 +71,8 @@ impl ::std::default::Default for StatefulSetUpdateStrategy {
 pub struct RollingUpdateStatefulSetStrategy {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub partition: Option<i32>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -86,6 +95,8 @@ pub struct StatefulSetStatus {
     pub collision_count: Option<i32>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub conditions: Vec<StatefulSetCondition>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 type StatefulSetConditionType = String;
@@ -96,10 +107,12 @@ pub struct StatefulSetCondition {
     #[serde(rename = "type")]
     pub condition_type: StatefulSetConditionType,
     pub status: api::core::v1::ConditionStatus,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
     pub last_transition_time: Option<meta::v1::Time>,
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub reason: String,
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub message: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
@@ -1,3 +1,5 @@
 pub mod v1;
 
 pub use self::v1::*;
+
+pub const API_GROUP: &str = "authentication.k8s.io";
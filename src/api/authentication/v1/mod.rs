@@ -1,9 +1,14 @@
 use std::collections::HashMap;
 
+use uuid::Uuid;
+
+use crate::apimachinery::apis::meta;
+use super::API_GROUP;
+pub const API_VERSION: &str = "v1";
 
 type ExtraValue = Vec<String>;
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct UserInfo {
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub username: String,
@@ -14,3 +19,123 @@ pub struct UserInfo {
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub extra: HashMap<String, ExtraValue>,
 }
+
+/// Asks the API server to authenticate a token, e.g. to delegate authentication of requests
+/// that carry service account tokens
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct TokenReview {
+    #[serde(flatten)]
+    pub type_meta: meta::v1::TypeMeta,
+    #[serde(default)]
+    pub metadata: meta::v1::ObjectMeta,
+    pub spec: TokenReviewSpec,
+    #[serde(default)]
+    pub status: TokenReviewStatus,
+}
+
+kube_kind!(TokenReview, TokenReviewList, "tokenreviews", Cluster);
+
+impl TokenReview {
+    pub fn new(token: &str, audiences: &[&str]) -> TokenReview {
+        TokenReview {
+            type_meta: meta::v1::TypeMeta {
+                kind: "TokenReview".to_string(),
+                api_version: format!("{}/{}", API_GROUP, API_VERSION),
+            },
+            spec: TokenReviewSpec {
+                token: token.to_string(),
+                audiences: audiences.iter().map(|audience| audience.to_string()).collect(),
+            },
+            ..Default::default()
+        }
+    }
+}
+
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct TokenReviewSpec {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub token: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub audiences: Vec<String>,
+}
+
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct TokenReviewStatus {
+    #[serde(default)]
+    pub authenticated: bool,
+    #[serde(default)]
+    pub user: UserInfo,
+    /// The audiences of the token that are also in the requested audiences
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub audiences: Vec<String>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub error: String,
+}
+
+/// Requests a token for a service account, see `KubeClient::create_token`
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct TokenRequest {
+    #[serde(flatten)]
+    pub type_meta: meta::v1::TypeMeta,
+    #[serde(default)]
+    pub metadata: meta::v1::ObjectMeta,
+    pub spec: TokenRequestSpec,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<TokenRequestStatus>,
+}
+
+impl TokenRequest {
+    /// A request for a token valid for the given audiences, the API server's own audience if
+    /// empty
+    pub fn new(audiences: &[&str], expiration_seconds: Option<i64>) -> TokenRequest {
+        TokenRequest {
+            type_meta: meta::v1::TypeMeta {
+                kind: "TokenRequest".to_string(),
+                api_version: format!("{}/{}", API_GROUP, API_VERSION),
+            },
+            spec: TokenRequestSpec {
+                audiences: audiences.iter().map(|audience| audience.to_string()).collect(),
+                expiration_seconds,
+                bound_object_ref: None,
+            },
+            ..Default::default()
+        }
+    }
+}
+
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct TokenRequestSpec {
+    #[serde(default)]
+    pub audiences: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expiration_seconds: Option<i64>,
+    /// The token is only valid as long as this object exists
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bound_object_ref: Option<BoundObjectReference>,
+}
+
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct BoundObjectReference {
+    /// `Pod` or `Secret`
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub kind: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub api_version: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Uuid::is_nil")]
+    pub uid: Uuid,
+}
+
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TokenRequestStatus {
+    pub token: String,
+    pub expiration_timestamp: meta::v1::Time,
+}
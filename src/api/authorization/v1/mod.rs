@@ -0,0 +1,240 @@
+use std::collections::HashMap;
+
+use crate::apimachinery::apis::meta;
+use super::API_GROUP;
+pub const API_VERSION: &str = "v1";
+
+type ExtraValue = Vec<String>;
+
+fn review_type_meta(kind: &str) -> meta::v1::TypeMeta {
+    meta::v1::TypeMeta {
+        kind: kind.to_string(),
+        api_version: format!("{}/{}", API_GROUP, API_VERSION),
+    }
+}
+
+/// Checks whether a user may perform an action
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct SubjectAccessReview {
+    #[serde(flatten)]
+    pub type_meta: meta::v1::TypeMeta,
+    #[serde(default)]
+    pub metadata: meta::v1::ObjectMeta,
+    pub spec: SubjectAccessReviewSpec,
+    #[serde(default)]
+    pub status: SubjectAccessReviewStatus,
+}
+
+kube_kind!(SubjectAccessReview, SubjectAccessReviewList, "subjectaccessreviews", Cluster);
+
+/// Like `SubjectAccessReview`, but restricted to a single namespace
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct LocalSubjectAccessReview {
+    #[serde(flatten)]
+    pub type_meta: meta::v1::TypeMeta,
+    #[serde(default)]
+    pub metadata: meta::v1::ObjectMeta,
+    pub spec: SubjectAccessReviewSpec,
+    #[serde(default)]
+    pub status: SubjectAccessReviewStatus,
+}
+
+kube_kind!(LocalSubjectAccessReview, LocalSubjectAccessReviewList, "localsubjectaccessreviews", Namespaced);
+
+/// Checks whether the current user may perform an action
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct SelfSubjectAccessReview {
+    #[serde(flatten)]
+    pub type_meta: meta::v1::TypeMeta,
+    #[serde(default)]
+    pub metadata: meta::v1::ObjectMeta,
+    pub spec: SelfSubjectAccessReviewSpec,
+    #[serde(default)]
+    pub status: SubjectAccessReviewStatus,
+}
+
+kube_kind!(SelfSubjectAccessReview, SelfSubjectAccessReviewList, "selfsubjectaccessreviews", Cluster);
+
+/// Lists the actions the current user may perform in a namespace
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct SelfSubjectRulesReview {
+    #[serde(flatten)]
+    pub type_meta: meta::v1::TypeMeta,
+    #[serde(default)]
+    pub metadata: meta::v1::ObjectMeta,
+    pub spec: SelfSubjectRulesReviewSpec,
+    #[serde(default)]
+    pub status: SubjectRulesReviewStatus,
+}
+
+kube_kind!(SelfSubjectRulesReview, SelfSubjectRulesReviewList, "selfsubjectrulesreviews", Cluster);
+
+/// An action on a resource. Empty fields match everything, except `namespace` which is empty for
+/// cluster-scoped resources and means all namespaces for namespaced ones.
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct ResourceAttributes {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub namespace: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub verb: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub group: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub version: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub resource: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub subresource: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub name: String,
+}
+
+impl ResourceAttributes {
+    /// Attributes in the form used by `kubectl auth can-i`: `resource` is `<resource>`,
+    /// `<resource>.<group>` or either followed by `/<subresource>`, e.g. `deployments.apps/scale`
+    pub fn new(verb: &str, resource: &str, namespace: Option<&str>) -> ResourceAttributes {
+        let (resource, subresource) = match resource.find('/') {
+            Some(index) => (&resource[..index], &resource[index + 1..]),
+            None => (resource, ""),
+        };
+        let (resource, group) = match resource.find('.') {
+            Some(index) => (&resource[..index], &resource[index + 1..]),
+            None => (resource, ""),
+        };
+        ResourceAttributes {
+            namespace: namespace.unwrap_or("").to_string(),
+            verb: verb.to_string(),
+            group: group.to_string(),
+            resource: resource.to_string(),
+            subresource: subresource.to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+/// An action on a path that isn't a resource, e.g. `/healthz`
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct NonResourceAttributes {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub path: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub verb: String,
+}
+
+/// Exactly one of `resource_attributes` and `non_resource_attributes` has to be set
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct SubjectAccessReviewSpec {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource_attributes: Option<ResourceAttributes>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub non_resource_attributes: Option<NonResourceAttributes>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub user: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub groups: Vec<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub extra: HashMap<String, ExtraValue>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub uid: String,
+}
+
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct SelfSubjectAccessReviewSpec {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource_attributes: Option<ResourceAttributes>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub non_resource_attributes: Option<NonResourceAttributes>,
+}
+
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct SubjectAccessReviewStatus {
+    #[serde(default)]
+    pub allowed: bool,
+    /// Set if an authorizer explicitly denied the action, `allowed` is false then too
+    #[serde(default, skip_serializing_if = "::std::ops::Not::not")]
+    pub denied: bool,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub reason: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub evaluation_error: String,
+}
+
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct SelfSubjectRulesReviewSpec {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub namespace: String,
+}
+
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct SubjectRulesReviewStatus {
+    #[serde(default)]
+    pub resource_rules: Vec<ResourceRule>,
+    #[serde(default)]
+    pub non_resource_rules: Vec<NonResourceRule>,
+    /// Set if the rules couldn't all be determined, e.g. with a webhook authorizer
+    #[serde(default)]
+    pub incomplete: bool,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub evaluation_error: String,
+}
+
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct ResourceRule {
+    pub verbs: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub api_groups: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub resources: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub resource_names: Vec<String>,
+}
+
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct NonResourceRule {
+    pub verbs: Vec<String>,
+    #[serde(rename = "nonResourceURLs", default, skip_serializing_if = "Vec::is_empty")]
+    pub non_resource_urls: Vec<String>,
+}
+
+impl SubjectAccessReview {
+    pub fn new(spec: SubjectAccessReviewSpec) -> SubjectAccessReview {
+        SubjectAccessReview {
+            type_meta: review_type_meta("SubjectAccessReview"),
+            spec,
+            ..Default::default()
+        }
+    }
+}
+
+impl SelfSubjectAccessReview {
+    pub fn new(spec: SelfSubjectAccessReviewSpec) -> SelfSubjectAccessReview {
+        SelfSubjectAccessReview {
+            type_meta: review_type_meta("SelfSubjectAccessReview"),
+            spec,
+            ..Default::default()
+        }
+    }
+}
+
+impl SelfSubjectRulesReview {
+    pub fn new(namespace: &str) -> SelfSubjectRulesReview {
+        SelfSubjectRulesReview {
+            type_meta: review_type_meta("SelfSubjectRulesReview"),
+            spec: SelfSubjectRulesReviewSpec { namespace: namespace.to_string() },
+            ..Default::default()
+        }
+    }
+}
@@ -16,9 +16,11 @@ pub struct Job {
     pub spec: JobSpec,
     #[serde(default)]
     pub status: JobStatus,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-kube_kind!(Job, JobList, "jobs");
+kube_kind!(Job, JobList, "jobs", Namespaced);
 
 #[serde(rename_all = "camelCase")]
 #[derive(Default, Serialize, Deserialize, Debug)]
@@ -36,6 +38,8 @@ pub struct JobSpec {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub manual_selector: Option<bool>,
     pub template: api::core::v1::PodTemplateSpec,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -53,6 +57,8 @@ pub struct JobStatus {
     pub succeeded: i32,
     #[serde(default, skip_serializing_if = "Zero::is_zero")]
     pub failed: i32,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 pub type JobConditionType = String;
@@ -63,12 +69,14 @@ pub struct JobCondition {
     #[serde(rename = "type")]
     pub condition_type: JobConditionType,
     pub status: api::core::v1::ConditionStatus,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
     pub last_transition_time: Option<meta::v1::Time>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
     pub last_probe_time: Option<meta::v1::Time>,
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub reason: String,
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub message: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
@@ -15,9 +15,11 @@ pub struct CronJob {
     pub spec: CronJobSpec,
     #[serde(default)]
     pub status: CronJobStatus,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-kube_kind!(CronJob, CronJobList, "cronjobs");
+kube_kind!(CronJob, CronJobList, "cronjobs", Namespaced);
 
 pub type ConcurrencyPolicy = String;
 
@@ -36,6 +38,8 @@ pub struct CronJobSpec {
     pub successful_jobs_history_limit: Option<i32>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub failed_jobs_history_limit: Option<i32>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -45,6 +49,8 @@ pub struct JobTemplateSpec {
     pub metadata: meta::v1::ObjectMeta,
     #[serde(default)]
     pub spec: api::batch::v1::JobSpec,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -54,4 +60,6 @@ pub struct CronJobStatus {
     pub active: Vec<api::core::v1::ObjectReference>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub last_schedule_time: Option<meta::v1::Time>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
@@ -0,0 +1,95 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::{Error as IoError, ErrorKind, Result as IoResult};
+use std::path::Path;
+
+use crate::apimachinery::apis::meta;
+use super::{API_GROUP, API_VERSION, SecretData};
+
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct ConfigMap {
+    #[serde(flatten)]
+    pub type_meta: meta::v1::TypeMeta,
+    #[serde(default)]
+    pub metadata: meta::v1::ObjectMeta,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub data: HashMap<String, String>,
+    /// Values that aren't valid UTF-8, base64 encoded like the data of secrets
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub binary_data: HashMap<String, SecretData>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub immutable: Option<bool>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+kube_kind!(ConfigMap, ConfigMapList, "configmaps", Namespaced);
+
+// Keys may only consist of alphanumerics, `-`, `_` and `.`
+fn is_valid_key(key: &str) -> bool {
+    !key.is_empty() && key != "." && key != ".." &&
+        key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+}
+
+impl ConfigMap {
+    pub fn new(name: &str) -> ConfigMap {
+        ConfigMap {
+            type_meta: meta::v1::TypeMeta {
+                kind: "ConfigMap".to_string(),
+                api_version: API_VERSION.to_string(),
+            },
+            metadata: meta::v1::ObjectMeta {
+                name: name.to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    /// Store the contents of a file under `key`. Like `kubectl create configmap --from-file`,
+    /// UTF-8 contents go to `data` and anything else to `binary_data`.
+    pub fn insert_file<P: AsRef<Path>>(&mut self, key: &str, path: P) -> IoResult<()> {
+        if !is_valid_key(key) {
+            return Err(IoError::new(ErrorKind::InvalidInput, format!("Invalid ConfigMap key `{}`", key)));
+        }
+        let contents = fs::read(path)?;
+        self.data.remove(key);
+        self.binary_data.remove(key);
+        match String::from_utf8(contents) {
+            Ok(text) => { self.data.insert(key.to_string(), text); },
+            Err(error) => { self.binary_data.insert(key.to_string(), SecretData(error.into_bytes())); },
+        }
+        Ok(())
+    }
+
+    /// A ConfigMap with the given files, each stored under its file name
+    pub fn from_files<P: AsRef<Path>>(name: &str, paths: &[P]) -> IoResult<ConfigMap> {
+        let mut config_map = ConfigMap::new(name);
+        for path in paths {
+            let path = path.as_ref();
+            let key = path.file_name()
+                .and_then(|file_name| file_name.to_str())
+                .ok_or_else(|| IoError::new(ErrorKind::InvalidInput, format!("No file name in {}", path.display())))?;
+            config_map.insert_file(key, path)?;
+        }
+        Ok(config_map)
+    }
+
+    /// A ConfigMap with every regular file of a directory, stored under its file name.
+    /// Subdirectories and files whose names aren't valid keys are skipped.
+    pub fn from_dir<P: AsRef<Path>>(name: &str, dir: P) -> IoResult<ConfigMap> {
+        let mut config_map = ConfigMap::new(name);
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            if !entry.file_type()?.is_file() {
+                continue;
+            }
+            match entry.file_name().to_str() {
+                Some(key) if is_valid_key(key) => config_map.insert_file(key, entry.path())?,
+                _ => debug!("Skipping {} as it isn't a valid ConfigMap key", entry.path().display()),
+            }
+        }
+        Ok(config_map)
+    }
+}
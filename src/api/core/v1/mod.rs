@@ -11,10 +11,20 @@ pub const API_VERSION: &str = "v1";
 mod pod;
 mod secret;
 mod node;
+mod service;
+mod endpoints;
+mod configmap;
+mod serviceaccount;
+mod volume;
 
 pub use self::pod::*;
 pub use self::secret::*;
 pub use self::node::*;
+pub use self::service::*;
+pub use self::endpoints::*;
+pub use self::configmap::*;
+pub use self::serviceaccount::*;
+pub use self::volume::*;
 
 
 // Namespace
@@ -25,12 +35,16 @@ pub type NamespacePhase = String;
 pub struct NamespaceSpec {
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub finalizers: Vec<FinalizerName>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct NamespaceStatus {
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub phase: NamespacePhase,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Default)]
@@ -43,9 +57,11 @@ pub struct Namespace {
     pub spec: NamespaceSpec,
     #[serde(default)]
     pub status: NamespaceStatus,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-kube_kind!(Namespace, NamespaceList, "namespaces");
+kube_kind!(Namespace, NamespaceList, "namespaces", Cluster);
 
 pub type ConditionStatus = String;
 
@@ -60,9 +76,11 @@ pub struct PersistentVolumeClaim {
     pub spec: PersistentVolumeClaimSpec,
     #[serde(default)]
     pub status: PersistentVolumeClaimStatus,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-kube_kind!(PersistentVolumeClaim, PersistentVolumeClaimList, "namespaces");
+kube_kind!(PersistentVolumeClaim, PersistentVolumeClaimList, "persistentvolumeclaims", Namespaced);
 
 pub type PersistentVolumeAccessMode = String;
 pub type PersistentVolumeMode = String;
@@ -74,6 +92,7 @@ pub struct PersistentVolumeClaimSpec {
     pub access_modes: Vec<PersistentVolumeAccessMode>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub selector: Option<meta::v1::LabelSelector>,
+    #[serde(default)]
     pub resources: ResourceRequirements,
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub volume_name: String,
@@ -81,6 +100,8 @@ pub struct PersistentVolumeClaimSpec {
     pub storage_class_name: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub volume_mode: Option<PersistentVolumeMode>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -90,6 +111,8 @@ pub struct ResourceRequirements {
     pub limits: ResourceList,
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub requests: ResourceList,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 type PersistentVolumeClaimPhase = String;
@@ -105,6 +128,8 @@ pub struct PersistentVolumeClaimStatus {
     pub capacity: ResourceList,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub conditions: Vec<PersistentVolumeClaimCondition>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 pub type PersistentVolumeClaimConditionType = String;
@@ -122,6 +147,8 @@ pub struct PersistentVolumeClaimCondition {
     pub reason: String,
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub message: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -141,4 +168,6 @@ pub struct ObjectReference {
     pub resource_version: String,
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub field_path: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
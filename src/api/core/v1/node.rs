@@ -16,9 +16,11 @@ pub struct Node {
     pub spec: NodeSpec,
     #[serde(default)]
     pub status: NodeStatus,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-kube_kind!(Node, NodeList, "nodes");
+kube_kind!(Node, NodeList, "nodes", Cluster);
 
 #[serde(rename_all = "camelCase")]
 #[derive(Debug, Serialize, Deserialize, Default)]
@@ -35,6 +37,8 @@ pub struct NodeSpec {
     pub config_source: Option<NodeConfigSource>,
     #[serde(rename = "externalID", default, skip_serializing_if = "String::is_empty")]
     pub external_id: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 pub type TaintEffect = String;
@@ -48,6 +52,8 @@ pub struct Taint {
     pub effect: TaintEffect,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub time_added: Option<meta::v1::Time>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -55,6 +61,8 @@ pub struct Taint {
 pub struct NodeConfigSource {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub config_map: Option<ConfigMapNodeConfigSource>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -67,6 +75,8 @@ pub struct ConfigMapNodeConfigSource {
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub resource_version: String,
     pub kubelet_config_key: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 pub type NodePhase = String;
@@ -97,6 +107,8 @@ pub struct NodeStatus {
     pub volumes_attached: Vec<AttachedVolume>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub config: Option<NodeConfigStatus>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 pub type NodeConditionType = String;
@@ -107,14 +119,16 @@ pub struct NodeCondition {
     #[serde(rename = "type")]
     pub condition_type: NodeConditionType,
     pub status: ConditionStatus,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
     pub last_heartbeat_time: Option<meta::v1::Time>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
     pub last_transition_time: Option<meta::v1::Time>,
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub reason: String,
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub message: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 pub type NodeAddressType = String;
@@ -125,6 +139,8 @@ pub struct NodeAddress {
     #[serde(rename = "type")]
     pub address_type: NodeAddressType,
     pub address: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -132,6 +148,8 @@ pub struct NodeAddress {
 pub struct NodeDaemonEndpoints {
     #[serde(default)]
     pub kubelet_endpoint: DaemonEndpoint,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -139,6 +157,8 @@ pub struct NodeDaemonEndpoints {
 pub struct DaemonEndpoint {
     #[serde(rename = "Port")]
     pub port: i32,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -157,6 +177,8 @@ pub struct NodeSystemInfo {
     pub kube_proxy_version: String,
     pub operating_system: String,
     pub architecture: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -165,6 +187,8 @@ pub struct ContainerImage {
     pub names: Vec<String>,
     #[serde(default, skip_serializing_if = "i64::is_zero")]
     pub size_bytes: i64,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -172,6 +196,8 @@ pub struct ContainerImage {
 pub struct AttachedVolume {
     pub name: UniqueVolumeName,
     pub device_path: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -185,4 +211,6 @@ pub struct NodeConfigStatus {
     pub last_known_good: Option<NodeConfigSource>,
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub error: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
@@ -1,9 +1,13 @@
 use std::collections::HashMap;
+use std::convert::TryFrom;
+
+use serde_json;
 
 use crate::apimachinery::apis::meta;
 use crate::apimachinery::api::resource;
 use crate::apimachinery::util::IntOrString;
 use super::{API_GROUP, API_VERSION};
+use super::volume::*;
 
 #[serde(rename_all = "camelCase")]
 #[derive(Serialize, Deserialize, Debug)]
@@ -16,9 +20,11 @@ pub struct Pod {
     pub spec: PodSpec,
     #[serde(default)]
     pub status: PodStatus,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-kube_kind!(Pod, PodList, "pods");
+kube_kind!(Pod, PodList, "pods", Namespaced);
 
 pub type PodPhase = String;
 
@@ -49,6 +55,8 @@ pub struct PodStatus {
     pub container_statuses: Vec<ContainerStatus>,
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub qos_class: PodQOSClass,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 type PodConditionType = String;
@@ -61,14 +69,16 @@ pub struct PodCondition {
     #[serde(rename = "type")]
     pub condition_type: PodConditionType,
     pub status: ConditionStatus,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
     pub last_probe_time: Option<meta::v1::Time>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
     pub last_transition_time: Option<meta::v1::Time>,
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub reason: String,
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub message: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -78,7 +88,7 @@ pub struct ContainerStatus {
     #[serde(default)]
     pub state: ContainerState,
     #[serde(default)]
-    pub last_termination_state: ContainerState,
+    pub last_state: ContainerState,
     pub ready: bool,
     pub restart_count: i32,
     pub image: String,
@@ -86,6 +96,10 @@ pub struct ContainerStatus {
     pub image_id: String,
     #[serde(rename = "containerID", default, skip_serializing_if = "String::is_empty")]
     pub container_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub started: Option<bool>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 // TODO: this could be represented way more effectively with an enum
@@ -98,6 +112,8 @@ pub struct ContainerState {
     pub running: Option<ContainerStateRunning>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub terminated: Option<ContainerStateTerminated>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -107,13 +123,17 @@ pub struct ContainerStateWaiting {
     pub reason: String,
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub message: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ContainerStateRunning {
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
     pub started_at: Option<meta::v1::Time>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -126,12 +146,14 @@ pub struct ContainerStateTerminated {
     pub reason: String,
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub message: String,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
     pub started_at: Option<meta::v1::Time>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
     pub finished_at: Option<meta::v1::Time>,
     #[serde(rename = "containerID", skip_serializing_if = "String::is_empty")]
     pub container_id: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 type DnsPolicy = String;
@@ -194,6 +216,8 @@ pub struct PodSpec {
     pub priority: Option<i32>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub dns_config: Option<PodDnsConfig>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -214,6 +238,8 @@ pub struct KeyToPath {
     pub path: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub mode: Option<i32>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -230,7 +256,8 @@ pub enum VolumeSource {
     EmptyDir {
         #[serde(default, skip_serializing_if = "String::is_empty")]
         medium: StorageMedium,
-        // TODO: sizeLimit field
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        size_limit: Option<resource::Quantity>,
     },
     #[serde(rename_all = "camelCase")]
     Secret {
@@ -249,8 +276,12 @@ pub enum VolumeSource {
         #[serde(default, skip_serializing_if = "::std::ops::Not::not")]
         read_only: bool,
     },
+    #[serde(rename_all = "camelCase")]
     DownwardAPI {
-
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        items: Vec<DownwardAPIVolumeFile>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        default_mode: Option<i32>,
     },
     #[serde(rename_all = "camelCase")]
     ConfigMap {
@@ -263,14 +294,126 @@ pub enum VolumeSource {
         #[serde(default, skip_serializing_if = "Option::is_none")]
         optional: Option<bool>,
     },
+    #[serde(rename_all = "camelCase")]
     Projected {
-
+        #[serde(default)]
+        sources: Vec<VolumeProjection>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        default_mode: Option<i32>,
     },
-    // TODO: rest of the volume types
+    GcePersistentDisk(GCEPersistentDiskVolumeSource),
+    AwsElasticBlockStore(AWSElasticBlockStoreVolumeSource),
+    GitRepo(GitRepoVolumeSource),
+    Nfs(NFSVolumeSource),
+    Iscsi(ISCSIVolumeSource),
+    Glusterfs(GlusterfsVolumeSource),
+    Rbd(RBDVolumeSource),
+    FlexVolume(FlexVolumeSource),
+    Cinder(CinderVolumeSource),
+    #[serde(rename = "cephfs")]
+    CephFS(CephFSVolumeSource),
+    Flocker(FlockerVolumeSource),
+    Fc(FCVolumeSource),
+    AzureFile(AzureFileVolumeSource),
+    VsphereVolume(VsphereVirtualDiskVolumeSource),
+    Quobyte(QuobyteVolumeSource),
+    AzureDisk(AzureDiskVolumeSource),
+    PhotonPersistentDisk(PhotonPersistentDiskVolumeSource),
+    PortworxVolume(PortworxVolumeSource),
+    #[serde(rename = "scaleIO")]
+    ScaleIO(ScaleIOVolumeSource),
+    #[serde(rename = "storageos")]
+    StorageOS(StorageOSVolumeSource),
+    Csi(CSIVolumeSource),
+    Ephemeral(EphemeralVolumeSource),
+    Image(ImageVolumeSource),
+    /// A volume source this library doesn't know, kept as JSON so it survives a round trip
+    #[serde(untagged)]
+    Other(HashMap<String, serde_json::Value>),
+}
+
+/// Exactly one of the fields is set
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct VolumeProjection {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret: Option<SecretProjection>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config_map: Option<ConfigMapProjection>,
+    #[serde(rename = "downwardAPI", default, skip_serializing_if = "Option::is_none")]
+    pub downward_api: Option<DownwardAPIProjection>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub service_account_token: Option<ServiceAccountTokenProjection>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SecretProjection {
+    #[serde(flatten)]
+    pub reference: LocalObjectReference,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub items: Vec<KeyToPath>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub optional: Option<bool>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ConfigMapProjection {
+    #[serde(flatten)]
+    pub reference: LocalObjectReference,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub items: Vec<KeyToPath>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub optional: Option<bool>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct DownwardAPIProjection {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub items: Vec<DownwardAPIVolumeFile>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DownwardAPIVolumeFile {
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub field_ref: Option<ObjectFieldSelector>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource_field_ref: Option<ResourceFieldSelector>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mode: Option<i32>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// A bound token of the pod's service account, rotated by the kubelet before it expires
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ServiceAccountTokenProjection {
+    /// Defaults to the audience of the API server
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub audience: String,
+    /// Defaults to one hour
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expiration_seconds: Option<i64>,
+    pub path: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 type TerminationMessagePolicy = String;
-type PullPolicy = String;
+pub type PullPolicy = String;
 
 #[serde(rename_all = "camelCase")]
 #[derive(Serialize, Deserialize, Debug)]
@@ -316,9 +459,25 @@ pub struct Container {
     pub stdin_once: bool,
     #[serde(default, skip_serializing_if = "::std::ops::Not::not")]
     pub tty: bool,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-type Protocol = String;
+/// `TCP`, `UDP` or `SCTP`
+pub type Protocol = String;
+
+impl Container {
+    /// Resolve a port that is either a number or the name of one of the container's ports. Numbers
+    /// that don't fit in an `i32` resolve to `None`
+    pub fn find_port(&self, port: &IntOrString) -> Option<i32> {
+        match port {
+            IntOrString::Int(number) => i32::try_from(*number).ok(),
+            IntOrString::String(name) => self.ports.iter()
+                .find(|container_port| container_port.name == *name)
+                .map(|container_port| container_port.container_port),
+        }
+    }
+}
 
 #[serde(rename_all = "camelCase")]
 #[derive(Serialize, Deserialize, Debug)]
@@ -332,6 +491,8 @@ pub struct ContainerPort {
     pub protocol: Protocol,
     #[serde(rename = "hostIP", default, skip_serializing_if = "String::is_empty")]
     pub host_ip: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -343,6 +504,8 @@ pub struct EnvFromSource {
     pub config_map_ref: Option<ConfigMapEnvSource>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub secret_ref: Option<SecretEnvSource>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -352,6 +515,8 @@ pub struct ConfigMapEnvSource {
     pub reference: LocalObjectReference,
     #[serde(default)]
     pub optional: bool,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -361,6 +526,8 @@ pub struct SecretEnvSource {
     pub reference: LocalObjectReference,
     #[serde(default)]
     pub optional: bool,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -378,6 +545,8 @@ pub struct EnvVar {
     pub value: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub value_from: Option<EnvVarSource>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -395,6 +564,8 @@ pub struct ObjectFieldSelector {
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub api_version: String,
     pub field_path: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -403,8 +574,10 @@ pub struct ResourceFieldSelector {
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub container_name: String,
     pub resource: String,
-    // TODO: another API?
-    // pub divisor: Option<resource::Quantity>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub divisor: Option<resource::Quantity>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -415,6 +588,8 @@ pub struct ConfigMapKeySelector {
     pub key: String,
     #[serde(default)]
     pub optional: bool,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -425,6 +600,8 @@ pub struct SecretKeySelector {
     pub key: String,
     #[serde(default)]
     pub optional: bool,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -434,6 +611,8 @@ pub struct ResourceRequirements {
     pub limits: resource::ResourceList,
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub requests: resource::ResourceList,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 type MountPropagationMode = String;
@@ -449,6 +628,8 @@ pub struct VolumeMount {
     pub sub_path: String,
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub mount_propagation: MountPropagationMode,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -456,6 +637,8 @@ pub struct VolumeMount {
 pub struct VolumeDevice {
     pub name: String,
     pub device_path: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -473,6 +656,8 @@ pub struct Probe {
     pub success_threshold: Option<i32>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub failure_threshold: Option<i32>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 type UriScheme = String;
@@ -507,6 +692,8 @@ pub enum Handler {
 pub struct HttpHeader {
     name: String,
     value: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -516,6 +703,8 @@ pub struct Lifecycle {
     pub post_start: Option<Handler>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub pre_stop: Option<Handler>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -537,6 +726,8 @@ pub struct SecurityContext {
     pub read_only_root_filesystem: Option<bool>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub allow_privilege_escalaltion: Option<bool>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 type Capability = String;
@@ -548,6 +739,8 @@ pub struct Capabilities {
     pub add: Vec<Capability>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub drop: Vec<Capability>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -561,6 +754,8 @@ pub struct SeLinuxOptions {
     pub selinux_type: String,
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub level: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -578,6 +773,8 @@ pub struct PodSecurityContext {
     pub supplemental_groups: Vec<i64>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub fs_groups: Option<i64>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -589,6 +786,8 @@ pub struct Affinity {
     pub pod_affinity: Option<PodAffinity>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub pod_anti_affinity: Option<PodAntiAffinity>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -598,12 +797,16 @@ pub struct NodeAffinity {
     pub required_during_scheduling_ignored_during_execution: Option<NodeSelector>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub preferred_during_scheduling_ignored_during_execution: Vec<PreferredSchedulingTerm>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
 #[derive(Serialize, Deserialize, Debug)]
 pub struct NodeSelector {
     pub node_selector_terms: Vec<NodeSelectorTerm>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 
@@ -611,6 +814,8 @@ pub struct NodeSelector {
 #[derive(Serialize, Deserialize, Debug)]
 pub struct NodeSelectorTerm {
     pub match_expressions: Vec<NodeSelectorRequirement>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 type NodeSelectorOperator = String;
@@ -622,6 +827,8 @@ pub struct NodeSelectorRequirement {
     pub operator: NodeSelectorOperator,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub values: Vec<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -629,6 +836,8 @@ pub struct NodeSelectorRequirement {
 pub struct PreferredSchedulingTerm {
     pub weight: i32,
     pub preference: NodeSelectorTerm,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -638,6 +847,8 @@ pub struct PodAffinity {
     pub required_during_scheduling_ignored_during_execution: Vec<PodAffinityTerm>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub preferred_during_scheduling_ignored_during_execution: Vec<WeightedPodAffinityTerm>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -647,6 +858,8 @@ pub struct PodAntiAffinity {
     pub required_during_scheduling_ignored_during_execution: Vec<PodAffinityTerm>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub preferred_during_scheduling_ignored_during_execution: Vec<WeightedPodAffinityTerm>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -657,6 +870,8 @@ pub struct PodAffinityTerm {
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub namespaces: Vec<String>,
     pub topology_key: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -664,6 +879,8 @@ pub struct PodAffinityTerm {
 pub struct WeightedPodAffinityTerm {
     pub weight: i32,
     pub pod_affinity_term: PodAffinityTerm,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 type TolerationOperator = String;
@@ -675,13 +892,15 @@ pub struct Toleration {
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub key: String,
     #[serde(default, skip_serializing_if = "String::is_empty")]
-    pub operation: TolerationOperator,
+    pub operator: TolerationOperator,
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub value: String,
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub effect: TaintEffect,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub toleration_seconds: Option<i64>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -691,6 +910,8 @@ pub struct HostAlias {
     pub ip: String,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub hostnames: Vec<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -702,6 +923,8 @@ pub struct PodDnsConfig {
     pub searches: Vec<String>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub options: Vec<PodDnsConfigOption>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -711,6 +934,8 @@ pub struct PodDnsConfigOption {
     pub name: String,
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub value: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -720,4 +945,6 @@ pub struct PodTemplateSpec {
     pub metadata: meta::v1::ObjectMeta,
     #[serde(default)]
     pub spec: PodSpec,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
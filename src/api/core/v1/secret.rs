@@ -67,6 +67,8 @@ pub struct Secret {
     pub data: HashMap<String, SecretData>,
     #[serde(rename = "type")]
     pub secret_type: Option<SecretType>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-kube_kind!(Secret, SecretList, "secrets");
+kube_kind!(Secret, SecretList, "secrets", Namespaced);
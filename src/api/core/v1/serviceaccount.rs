@@ -0,0 +1,37 @@
+use crate::apimachinery::apis::meta;
+use super::{API_GROUP, API_VERSION, LocalObjectReference, ObjectReference};
+
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct ServiceAccount {
+    #[serde(flatten)]
+    pub type_meta: meta::v1::TypeMeta,
+    #[serde(default)]
+    pub metadata: meta::v1::ObjectMeta,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub secrets: Vec<ObjectReference>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub image_pull_secrets: Vec<LocalObjectReference>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub automount_service_account_token: Option<bool>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+kube_kind!(ServiceAccount, ServiceAccountList, "serviceaccounts", Namespaced);
+
+impl ServiceAccount {
+    pub fn new(name: &str) -> ServiceAccount {
+        ServiceAccount {
+            type_meta: meta::v1::TypeMeta {
+                kind: "ServiceAccount".to_string(),
+                api_version: API_VERSION.to_string(),
+            },
+            metadata: meta::v1::ObjectMeta {
+                name: name.to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+}
@@ -0,0 +1,85 @@
+//! Untyped objects, for resources that have no Rust type, e.g. custom resources or objects read
+//! from arbitrary manifests.
+use serde_json::{Map, Value};
+
+use crate::api::{KubeKind, Scope};
+use crate::apimachinery::apis::meta;
+
+/// Describes a resource whose type is only known at runtime
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ApiResource {
+    /// API group, `core` (or the empty string) for the core group
+    pub group: String,
+    pub version: String,
+    pub kind: String,
+    /// The plural name used in URLs, e.g. `deployments`
+    pub plural: String,
+    pub namespaced: bool,
+}
+
+impl ApiResource {
+    pub fn new(group: &str, version: &str, kind: &str, plural: &str, namespaced: bool) -> ApiResource {
+        ApiResource {
+            group: group.to_string(),
+            version: version.to_string(),
+            kind: kind.to_string(),
+            plural: plural.to_string(),
+            namespaced,
+        }
+    }
+
+    /// Describe a typed resource. The kind is not known by `KubeKind` so it has to be supplied.
+    pub fn from_kube_kind<T: KubeKind>(kind: &str) -> ApiResource {
+        ApiResource::new(T::API_GROUP, T::API_VERSION, kind, T::KIND_NAME, T::Scope::NAMESPACED)
+    }
+
+    pub fn is_core(&self) -> bool {
+        self.group.is_empty() || self.group == "core"
+    }
+
+    /// The `apiVersion` of objects of this resource, e.g. `apps/v1` or `v1` for the core group
+    pub fn api_version(&self) -> String {
+        if self.is_core() {
+            self.version.clone()
+        } else {
+            format!("{}/{}", self.group, self.version)
+        }
+    }
+}
+
+/// An object of any kind. Everything other than the type and object metadata is kept as JSON.
+#[serde(rename_all = "camelCase")]
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct DynamicObject {
+    #[serde(flatten)]
+    pub type_meta: meta::v1::TypeMeta,
+    #[serde(default)]
+    pub metadata: meta::v1::ObjectMeta,
+    #[serde(flatten)]
+    pub data: Map<String, Value>,
+}
+
+impl DynamicObject {
+    /// Create an empty object of the given resource
+    pub fn new(resource: &ApiResource, name: &str) -> DynamicObject {
+        DynamicObject {
+            type_meta: meta::v1::TypeMeta {
+                kind: resource.kind.clone(),
+                api_version: resource.api_version(),
+            },
+            metadata: meta::v1::ObjectMeta {
+                name: name.to_string(),
+                ..Default::default()
+            },
+            data: Map::new(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DynamicObjectList {
+    #[serde(flatten)]
+    pub type_meta: meta::v1::TypeMeta,
+    pub items: Vec<DynamicObject>,
+    pub metadata: meta::v1::ListMeta,
+}
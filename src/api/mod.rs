@@ -1,26 +1,32 @@
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 
-///! NOTE: Go uses `omitempty` for many fields, i.e. if the string is empty the field will be left
-///! out of the serialized version. Serde's "default" feature maps to this behavior but I'm not 100%
-///! convinced this is the right way to go.
-///! While this way maps closest to the way the Go code is written it is not ideal. The k8s Go API
-///! is a mess, because in some places `string` is used, where the empty string is treated as the
-///! absence of value, whereas in other places `*string` is used, which is more akin to
-///! Option<String> in Rust.
-///! To match the behavior the best go's `string` is mapped to `String` and `*string` to
-///! `Option<String>`.
-///! Other types will be treated on a case-by-case basis as described here:
-///! * Uuid - Will be treated regularly, should be compared with `Uuid::is_nil`
+// NOTE: Go uses `omitempty` for many fields, i.e. if the string is empty the field will be left
+// out of the serialized version. Serde's "default" feature maps to this behavior but I'm not 100%
+// convinced this is the right way to go.
+// While this way maps closest to the way the Go code is written it is not ideal. The k8s Go API
+// is a mess, because in some places `string` is used, where the empty string is treated as the
+// absence of value, whereas in other places `*string` is used, which is more akin to
+// Option<String> in Rust.
+// To match the behavior the best go's `string` is mapped to `String` and `*string` to
+// `Option<String>`.
+// Other types will be treated on a case-by-case basis as described here:
+// * Uuid - Will be treated regularly, should be compared with `Uuid::is_nil`
+// * Time - Go's `metav1.Time` is written as `null` when it's zero, so fields that aren't pointers
+//   in Go are `Option<Time>` that are always serialized
+// Fields that aren't modeled are collected in a flattened `extra` map on the structs of the
+// resources, apart from deprecated beta versions, so reading an object and replacing it doesn't
+// drop what the server set.
 
 macro_rules! kube_kind {
-    ( $typ:ty, $list_name:ident, $name: expr ) => {
+    ( $typ:ty, $list_name:ident, $name: expr, $scope:ident ) => {
         impl crate::api::KubeKind for $typ {
             const KIND_NAME: &'static str = $name;
             const API_GROUP: &'static str = API_GROUP;
             const API_VERSION: &'static str = API_VERSION;
 
             type List = $list_name;
+            type Scope = crate::api::$scope;
         }
 
         #[derive(Debug, Serialize, Deserialize)]
@@ -34,10 +40,14 @@ macro_rules! kube_kind {
 }
 
 pub mod admission;
+pub mod apiextensions;
 pub mod apps;
 pub mod authentication;
+pub mod authorization;
 pub mod batch;
 pub mod core;
+pub mod discovery;
+pub mod dynamic;
 pub mod rbac;
 
 
@@ -52,4 +62,39 @@ pub trait KubeKind: DeserializeOwned + Serialize {
     const API_VERSION: &'static str;
 
     type List: DeserializeOwned;
+    /// Either `Namespaced` or `Cluster`, restricts which client methods accept the kind
+    type Scope: Scope;
+}
+
+/// The scope of a kind, see `Namespaced` and `Cluster`
+pub trait Scope {
+    const NAMESPACED: bool;
+}
+
+/// Scope of kinds that live in a namespace, e.g. pods
+pub enum Namespaced {}
+
+/// Scope of cluster-wide kinds, e.g. nodes
+pub enum Cluster {}
+
+impl Scope for Namespaced {
+    const NAMESPACED: bool = true;
+}
+
+impl Scope for Cluster {
+    const NAMESPACED: bool = false;
+}
+
+/// Trait implemented by custom resources, in addition to `KubeKind`.
+/// Generally you should use `#[derive(CustomResource)]` from the `derive` feature to implement this.
+pub trait CustomResource: KubeKind {
+    /// The kind of the resource, e.g. `CronTab`. Unlike `KIND_NAME` this is not the plural.
+    const KIND: &'static str;
+    const SHORT_NAMES: &'static [&'static str];
+    /// Whether the resource has the status subresource enabled
+    const STATUS_SUBRESOURCE: bool;
+
+    type Spec;
+    /// `()` for resources without a status
+    type Status;
 }
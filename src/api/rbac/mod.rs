@@ -1,5 +1,6 @@
 pub const API_GROUP: &str = "rbac.authorization.k8s.io";
 
+pub mod v1;
 pub mod v1beta1;
 
 pub use self::v1beta1::*;
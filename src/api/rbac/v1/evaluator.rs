@@ -0,0 +1,259 @@
+//! Local evaluation of RBAC rules, following the RBAC authorizer of the API server.
+use crate::api::authorization::v1::{NonResourceAttributes, ResourceAttributes};
+use crate::apimachinery::labels::Selector;
+use super::*;
+
+/// A user as seen by the authorizer
+#[derive(Debug, Clone, Default)]
+pub struct UserIdentity {
+    pub name: String,
+    pub groups: Vec<String>,
+}
+
+impl UserIdentity {
+    pub fn new(name: &str, groups: &[&str]) -> UserIdentity {
+        UserIdentity {
+            name: name.to_string(),
+            groups: groups.iter().map(|group| group.to_string()).collect(),
+        }
+    }
+
+    /// The identity of a subject: service accounts authenticate as
+    /// `system:serviceaccount:<namespace>:<name>` and belong to the `system:serviceaccounts`
+    /// groups, a group subject is a member of that group only.
+    pub fn from_subject(subject: &Subject) -> UserIdentity {
+        match subject.kind.as_str() {
+            SERVICE_ACCOUNT_KIND => UserIdentity {
+                name: service_account_user(&subject.namespace, &subject.name),
+                groups: vec![
+                    "system:serviceaccounts".to_string(),
+                    format!("system:serviceaccounts:{}", subject.namespace),
+                    "system:authenticated".to_string(),
+                ],
+            },
+            GROUP_KIND => UserIdentity { name: String::new(), groups: vec![subject.name.clone()] },
+            _ => UserIdentity { name: subject.name.clone(), groups: Vec::new() },
+        }
+    }
+}
+
+fn service_account_user(namespace: &str, name: &str) -> String {
+    format!("system:serviceaccount:{}:{}", namespace, name)
+}
+
+// Whether a subject of a binding in `namespace` refers to the user
+fn applies_to(subject: &Subject, user: &UserIdentity, namespace: &str) -> bool {
+    match subject.kind.as_str() {
+        USER_KIND => !user.name.is_empty() && user.name == subject.name,
+        GROUP_KIND => user.groups.contains(&subject.name),
+        SERVICE_ACCOUNT_KIND => {
+            let namespace = if subject.namespace.is_empty() { namespace } else { &subject.namespace };
+            user.name == service_account_user(namespace, &subject.name)
+        },
+        _ => false,
+    }
+}
+
+fn matches_any(values: &[String], requested: &str) -> bool {
+    values.iter().any(|value| value == "*" || value == requested)
+}
+
+impl PolicyRule {
+    pub fn allows(&self, attributes: &ResourceAttributes) -> bool {
+        if !matches_any(&self.verbs, &attributes.verb) || !matches_any(&self.api_groups, &attributes.group) {
+            return false;
+        }
+        let resource_matches = if attributes.subresource.is_empty() {
+            matches_any(&self.resources, &attributes.resource)
+        } else {
+            let combined = format!("{}/{}", attributes.resource, attributes.subresource);
+            let any_resource = format!("*/{}", attributes.subresource);
+            self.resources.iter().any(|resource| *resource == "*" || *resource == combined || *resource == any_resource)
+        };
+        resource_matches && (self.resource_names.is_empty() || self.resource_names.contains(&attributes.name))
+    }
+
+    pub fn allows_non_resource(&self, attributes: &NonResourceAttributes) -> bool {
+        matches_any(&self.verbs, &attributes.verb) && self.non_resource_urls.iter().any(|url| {
+            if url.ends_with('*') {
+                attributes.path.starts_with(url.trim_end_matches('*'))
+            } else {
+                *url == attributes.path
+            }
+        })
+    }
+}
+
+/// Answers whether users may perform actions, given the roles and bindings of a cluster
+#[derive(Debug, Default)]
+pub struct RuleEvaluator {
+    pub roles: Vec<Role>,
+    pub cluster_roles: Vec<ClusterRole>,
+    pub role_bindings: Vec<RoleBinding>,
+    pub cluster_role_bindings: Vec<ClusterRoleBinding>,
+}
+
+impl RuleEvaluator {
+    pub fn new(roles: Vec<Role>, cluster_roles: Vec<ClusterRole>,
+               role_bindings: Vec<RoleBinding>, cluster_role_bindings: Vec<ClusterRoleBinding>) -> RuleEvaluator {
+        RuleEvaluator { roles, cluster_roles, role_bindings, cluster_role_bindings }
+    }
+
+    fn cluster_role(&self, name: &str) -> Option<&ClusterRole> {
+        self.cluster_roles.iter().find(|role| role.metadata.name == name)
+    }
+
+    /// The rules of a cluster role, including the rules of the cluster roles it aggregates in
+    /// case the controller manager hasn't filled them in
+    pub fn cluster_role_rules<'a>(&'a self, role: &'a ClusterRole) -> Vec<&'a PolicyRule> {
+        let mut rules: Vec<&PolicyRule> = role.rules.iter().collect();
+        if let Some(ref aggregation) = role.aggregation_rule {
+            let selectors: Vec<Selector> = aggregation.cluster_role_selectors.iter().map(Selector::from).collect();
+            for other in &self.cluster_roles {
+                if other.metadata.name != role.metadata.name && selectors.iter().any(|selector| selector.matches(&other.metadata.labels)) {
+                    rules.extend(other.rules.iter());
+                }
+            }
+        }
+        rules
+    }
+
+    // The rules granted by a role reference of a binding in `namespace`, empty for cluster bindings
+    fn referenced_rules(&self, role_ref: &RoleRef, namespace: &str) -> Vec<&PolicyRule> {
+        match role_ref.kind.as_str() {
+            "ClusterRole" => self.cluster_role(&role_ref.name)
+                .map(|role| self.cluster_role_rules(role))
+                .unwrap_or_default(),
+            "Role" if !namespace.is_empty() => self.roles.iter()
+                .filter(|role| role.metadata.namespace == namespace && role.metadata.name == role_ref.name)
+                .flat_map(|role| role.rules.iter())
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// All rules that apply to the user in a namespace, or only cluster-wide ones if `namespace`
+    /// is empty
+    pub fn rules_for(&self, user: &UserIdentity, namespace: &str) -> Vec<&PolicyRule> {
+        let mut rules = Vec::new();
+        for binding in &self.cluster_role_bindings {
+            if binding.subjects.iter().any(|subject| applies_to(subject, user, "")) {
+                rules.extend(self.referenced_rules(&binding.role_ref, ""));
+            }
+        }
+        if !namespace.is_empty() {
+            for binding in self.role_bindings.iter().filter(|binding| binding.metadata.namespace == namespace) {
+                if binding.subjects.iter().any(|subject| applies_to(subject, user, namespace)) {
+                    rules.extend(self.referenced_rules(&binding.role_ref, namespace));
+                }
+            }
+        }
+        rules
+    }
+
+    pub fn user_allows(&self, user: &UserIdentity, attributes: &ResourceAttributes) -> bool {
+        self.rules_for(user, &attributes.namespace).iter().any(|rule| rule.allows(attributes))
+    }
+
+    /// Non-resource URLs can only be granted by cluster role bindings
+    pub fn user_allows_non_resource(&self, user: &UserIdentity, attributes: &NonResourceAttributes) -> bool {
+        self.rules_for(user, "").iter().any(|rule| rule.allows_non_resource(attributes))
+    }
+
+    /// Whether the subject may perform the action, e.g.
+    /// `evaluator.allows(&Subject::user("jane"), &ResourceAttributes::new("get", "pods", Some("default")))`
+    pub fn allows(&self, subject: &Subject, attributes: &ResourceAttributes) -> bool {
+        self.user_allows(&UserIdentity::from_subject(subject), attributes)
+    }
+
+    pub fn allows_non_resource(&self, subject: &Subject, attributes: &NonResourceAttributes) -> bool {
+        self.user_allows_non_resource(&UserIdentity::from_subject(subject), attributes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn evaluator() -> RuleEvaluator {
+        let roles = vec![serde_json::from_value(json!({
+            "metadata": { "name": "pod-reader", "namespace": "default" },
+            "rules": [{ "apiGroups": [""], "resources": ["pods", "pods/log"], "verbs": ["get", "list"] }],
+        })).unwrap()];
+        let cluster_roles = vec![
+            serde_json::from_value(json!({
+                "metadata": { "name": "monitoring" },
+                "aggregationRule": {
+                    "clusterRoleSelectors": [{ "matchLabels": { "rbac.example.com/aggregate-to-monitoring": "true" } }],
+                },
+            })).unwrap(),
+            serde_json::from_value(json!({
+                "metadata": {
+                    "name": "monitoring-endpoints",
+                    "labels": { "rbac.example.com/aggregate-to-monitoring": "true" },
+                },
+                "rules": [
+                    { "apiGroups": [""], "resources": ["endpoints"], "verbs": ["get"] },
+                    { "nonResourceURLs": ["/metrics*"], "verbs": ["get"] },
+                ],
+            })).unwrap(),
+        ];
+        let role_bindings = vec![serde_json::from_value(json!({
+            "metadata": { "name": "read-pods", "namespace": "default" },
+            "subjects": [
+                { "kind": "User", "apiGroup": "rbac.authorization.k8s.io", "name": "jane" },
+                { "kind": "ServiceAccount", "name": "builder" },
+            ],
+            "roleRef": { "apiGroup": "rbac.authorization.k8s.io", "kind": "Role", "name": "pod-reader" },
+        })).unwrap()];
+        let cluster_role_bindings = vec![serde_json::from_value(json!({
+            "metadata": { "name": "monitoring" },
+            "subjects": [{ "kind": "Group", "apiGroup": "rbac.authorization.k8s.io", "name": "monitors" }],
+            "roleRef": { "apiGroup": "rbac.authorization.k8s.io", "kind": "ClusterRole", "name": "monitoring" },
+        })).unwrap()];
+        RuleEvaluator::new(roles, cluster_roles, role_bindings, cluster_role_bindings)
+    }
+
+    #[test]
+    fn role_binding() {
+        let evaluator = evaluator();
+        let jane = Subject::user("jane");
+        assert!(evaluator.allows(&jane, &ResourceAttributes::new("get", "pods", Some("default"))));
+        assert!(evaluator.allows(&jane, &ResourceAttributes::new("list", "pods/log", Some("default"))));
+        assert!(!evaluator.allows(&jane, &ResourceAttributes::new("delete", "pods", Some("default"))));
+        assert!(!evaluator.allows(&jane, &ResourceAttributes::new("get", "pods", Some("kube-system"))));
+        assert!(!evaluator.allows(&jane, &ResourceAttributes::new("get", "pods/exec", Some("default"))));
+        assert!(!evaluator.allows(&Subject::user("joe"), &ResourceAttributes::new("get", "pods", Some("default"))));
+    }
+
+    #[test]
+    fn service_account_subject_defaults_to_binding_namespace() {
+        let evaluator = evaluator();
+        let attributes = ResourceAttributes::new("get", "pods", Some("default"));
+        assert!(evaluator.allows(&Subject::service_account("default", "builder"), &attributes));
+        assert!(!evaluator.allows(&Subject::service_account("ci", "builder"), &attributes));
+        let user = UserIdentity::new("system:serviceaccount:default:builder", &[]);
+        assert!(evaluator.user_allows(&user, &attributes));
+    }
+
+    #[test]
+    fn aggregated_cluster_role() {
+        let evaluator = evaluator();
+        let monitors = Subject::group("monitors");
+        let role = evaluator.cluster_role("monitoring").unwrap();
+        assert_eq!(evaluator.cluster_role_rules(role).len(), 2);
+        assert!(evaluator.allows(&monitors, &ResourceAttributes::new("get", "endpoints", Some("default"))));
+        assert!(evaluator.allows(&monitors, &ResourceAttributes::new("get", "endpoints", None)));
+        assert!(!evaluator.allows(&monitors, &ResourceAttributes::new("list", "endpoints", None)));
+        assert!(evaluator.allows_non_resource(&monitors, &NonResourceAttributes {
+            path: "/metrics/cadvisor".to_string(),
+            verb: "get".to_string(),
+        }));
+        assert!(!evaluator.allows_non_resource(&monitors, &NonResourceAttributes {
+            path: "/healthz".to_string(),
+            verb: "get".to_string(),
+        }));
+    }
+}
@@ -0,0 +1,164 @@
+use crate::apimachinery::apis::meta;
+
+use super::API_GROUP;
+pub const API_VERSION: &str = "v1";
+
+mod evaluator;
+
+pub use self::evaluator::*;
+
+pub const USER_KIND: &str = "User";
+pub const GROUP_KIND: &str = "Group";
+pub const SERVICE_ACCOUNT_KIND: &str = "ServiceAccount";
+
+#[serde(rename_all = "camelCase")]
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct Role {
+    #[serde(flatten)]
+    pub type_meta: meta::v1::TypeMeta,
+    #[serde(default)]
+    pub metadata: meta::v1::ObjectMeta,
+    #[serde(default)]
+    pub rules: Vec<PolicyRule>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+kube_kind!(Role, RoleList, "roles", Namespaced);
+
+#[serde(rename_all = "camelCase")]
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct ClusterRole {
+    #[serde(flatten)]
+    pub type_meta: meta::v1::TypeMeta,
+    #[serde(default)]
+    pub metadata: meta::v1::ObjectMeta,
+    /// Filled in by the controller manager if `aggregation_rule` is set
+    #[serde(default)]
+    pub rules: Vec<PolicyRule>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aggregation_rule: Option<AggregationRule>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+kube_kind!(ClusterRole, ClusterRoleList, "clusterroles", Cluster);
+
+/// Combines the rules of the cluster roles matching any of the selectors
+#[serde(rename_all = "camelCase")]
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct AggregationRule {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cluster_role_selectors: Vec<meta::v1::LabelSelector>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// `*` matches any verb, API group or resource. Resource names and non-resource URLs are only
+/// checked if they are given.
+#[serde(rename_all = "camelCase")]
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct PolicyRule {
+    pub verbs: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub api_groups: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub resources: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub resource_names: Vec<String>,
+    #[serde(rename = "nonResourceURLs", default, skip_serializing_if = "Vec::is_empty")]
+    pub non_resource_urls: Vec<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[serde(rename_all = "camelCase")]
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct RoleBinding {
+    #[serde(flatten)]
+    pub type_meta: meta::v1::TypeMeta,
+    #[serde(default)]
+    pub metadata: meta::v1::ObjectMeta,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub subjects: Vec<Subject>,
+    pub role_ref: RoleRef,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+kube_kind!(RoleBinding, RoleBindingList, "rolebindings", Namespaced);
+
+#[serde(rename_all = "camelCase")]
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct ClusterRoleBinding {
+    #[serde(flatten)]
+    pub type_meta: meta::v1::TypeMeta,
+    #[serde(default)]
+    pub metadata: meta::v1::ObjectMeta,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub subjects: Vec<Subject>,
+    pub role_ref: RoleRef,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+kube_kind!(ClusterRoleBinding, ClusterRoleBindingList, "clusterrolebindings", Cluster);
+
+#[serde(rename_all = "camelCase")]
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+pub struct Subject {
+    /// `User`, `Group` or `ServiceAccount`
+    pub kind: String,
+    /// Empty for service accounts, `rbac.authorization.k8s.io` otherwise
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub api_group: String,
+    pub name: String,
+    /// The namespace of a service account, defaults to that of the binding
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub namespace: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Subject {
+    pub fn user(name: &str) -> Subject {
+        Subject {
+            kind: USER_KIND.to_string(),
+            api_group: API_GROUP.to_string(),
+            name: name.to_string(),
+            namespace: String::new(),
+            extra: Default::default(),
+        }
+    }
+
+    pub fn group(name: &str) -> Subject {
+        Subject {
+            kind: GROUP_KIND.to_string(),
+            api_group: API_GROUP.to_string(),
+            name: name.to_string(),
+            namespace: String::new(),
+            extra: Default::default(),
+        }
+    }
+
+    pub fn service_account(namespace: &str, name: &str) -> Subject {
+        Subject {
+            kind: SERVICE_ACCOUNT_KIND.to_string(),
+            api_group: String::new(),
+            name: name.to_string(),
+            namespace: namespace.to_string(),
+            extra: Default::default(),
+        }
+    }
+}
+
+/// `kind` is `Role` or `ClusterRole`
+#[serde(rename_all = "camelCase")]
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+pub struct RoleRef {
+    pub api_group: String,
+    pub kind: String,
+    pub name: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
@@ -20,7 +20,7 @@ pub struct PolicyRule {
     pub non_resource_urls: Option<Vec<String>>,
 }
 
-kube_kind!(ClusterRole, ClusterRoleList, "clusterroles");
+kube_kind!(ClusterRole, ClusterRoleList, "clusterroles", Cluster);
 
 #[serde(rename_all = "camelCase")]
 #[derive(Debug, Serialize, Deserialize, Default)]
@@ -30,7 +30,7 @@ pub struct ClusterRoleBinding {
     pub role_ref: RoleRef,
 }
 
-kube_kind!(ClusterRoleBinding, ClusterRoleBindingList, "clusterrolebindings");
+kube_kind!(ClusterRoleBinding, ClusterRoleBindingList, "clusterrolebindings", Cluster);
 
 #[serde(rename_all = "camelCase")]
 #[derive(Debug, Serialize, Deserialize, Default)]
@@ -40,7 +40,7 @@ pub struct RoleBinding {
     pub role_ref: RoleRef,
 }
 
-kube_kind!(RoleBinding, RoleBindingList, "rolebindings");
+kube_kind!(RoleBinding, RoleBindingList, "rolebindings", Namespaced);
 
 #[serde(rename_all = "camelCase")]
 #[derive(Debug, Serialize, Deserialize, Default)]
@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
-use crate::apimachinery::util::IntOrString;
+mod quantity;
 
-pub type ResourceList = HashMap<String, IntOrString>;
+pub use self::quantity::*;
+
+pub type ResourceList = HashMap<String, Quantity>;
@@ -0,0 +1,578 @@
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::error::Error as StdError;
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Sub, SubAssign};
+use std::str::FromStr;
+
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+/// How a quantity is written, which is kept when it is serialized again
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QuantityFormat {
+    /// e.g. `12e6`
+    DecimalExponent,
+    /// e.g. `128Mi`
+    BinarySI,
+    /// e.g. `500m`, `12M`
+    DecimalSI,
+}
+
+/// A fixed-point number with a suffix, e.g. `500m` CPU or `1Gi` of memory.
+///
+/// The value is `mantissa * 10^exponent`, it is exact except that precision below `1n` is
+/// rounded up, like the API server does. Comparison and equality only consider the value, so
+/// `1Ki == 1024`.
+#[derive(Debug, Clone, Copy)]
+pub struct Quantity {
+    mantissa: i128,
+    exponent: i32,
+    format: QuantityFormat,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum QuantityParseError {
+    Empty,
+    InvalidNumber,
+    InvalidSuffix(String),
+    /// The value doesn't fit in the internal representation
+    OutOfRange,
+}
+
+impl fmt::Display for QuantityParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            QuantityParseError::Empty => write!(f, "Empty quantity"),
+            QuantityParseError::InvalidNumber => write!(f, "Invalid number in quantity"),
+            QuantityParseError::InvalidSuffix(suffix) => write!(f, "Invalid quantity suffix `{}`", suffix),
+            QuantityParseError::OutOfRange => write!(f, "Quantity out of range"),
+        }
+    }
+}
+
+impl StdError for QuantityParseError {}
+
+// The smallest representable unit is 1n
+const MIN_EXPONENT: i32 = -9;
+// The largest decimal SI suffix is E
+const MAX_SI_EXPONENT: i32 = 18;
+
+const DECIMAL_SUFFIXES: &[(&str, i32)] = &[
+    ("n", -9), ("u", -6), ("m", -3), ("", 0), ("k", 3), ("M", 6), ("G", 9), ("T", 12), ("P", 15), ("E", 18),
+];
+
+const BINARY_SUFFIXES: &[(&str, u32)] = &[
+    ("", 0), ("Ki", 10), ("Mi", 20), ("Gi", 30), ("Ti", 40), ("Pi", 50), ("Ei", 60),
+];
+
+// `10^exponent`, computed from an `i64` so that differences of exponents can't overflow
+fn pow10(exponent: i64) -> Option<i128> {
+    u32::try_from(exponent).ok().and_then(|exponent| 10i128.checked_pow(exponent))
+}
+
+// Divide, rounding away from zero
+fn div_round_up(value: i128, divisor: i128) -> i128 {
+    let quotient = value / divisor;
+    if value % divisor == 0 {
+        quotient
+    } else {
+        quotient + value.signum()
+    }
+}
+
+impl Quantity {
+    fn from_parts(mantissa: i128, exponent: i32, format: QuantityFormat) -> Quantity {
+        let (mut mantissa, mut exponent) = if exponent < MIN_EXPONENT {
+            match pow10(i64::from(MIN_EXPONENT) - i64::from(exponent)) {
+                Some(divisor) => (div_round_up(mantissa, divisor), MIN_EXPONENT),
+                // Less than 1n, rounded up to 1n
+                None => (mantissa.signum(), MIN_EXPONENT),
+            }
+        } else {
+            (mantissa, exponent)
+        };
+        if mantissa == 0 {
+            exponent = 0;
+        }
+        while mantissa != 0 && mantissa % 10 == 0 && exponent < i32::MAX {
+            mantissa /= 10;
+            exponent += 1;
+        }
+        Quantity { mantissa, exponent, format }
+    }
+
+    pub fn new(value: i64, format: QuantityFormat) -> Quantity {
+        Quantity::from_parts(i128::from(value), 0, format)
+    }
+
+    /// A quantity of `value` thousandths, e.g. millicores
+    pub fn new_milli(value: i64, format: QuantityFormat) -> Quantity {
+        Quantity::from_parts(i128::from(value), -3, format)
+    }
+
+    /// A decimal SI quantity of `value * 10^scale`
+    pub fn new_scaled(value: i64, scale: i32) -> Quantity {
+        Quantity::from_parts(i128::from(value), scale, QuantityFormat::DecimalSI)
+    }
+
+    pub fn zero() -> Quantity {
+        Quantity::new(0, QuantityFormat::DecimalSI)
+    }
+
+    pub fn format(&self) -> QuantityFormat {
+        self.format
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.mantissa == 0
+    }
+
+    // The mantissa when the value is written with the given exponent, if it's exact
+    fn mantissa_at(&self, exponent: i32) -> Option<i128> {
+        if exponent <= self.exponent {
+            self.mantissa.checked_mul(pow10(i64::from(self.exponent) - i64::from(exponent))?)
+        } else {
+            let divisor = pow10(i64::from(exponent) - i64::from(self.exponent))?;
+            if self.mantissa % divisor == 0 {
+                Some(self.mantissa / divisor)
+            } else {
+                None
+            }
+        }
+    }
+
+    // The value as a multiple of `10^exponent`, rounded away from zero
+    fn scaled_value(&self, exponent: i32) -> i64 {
+        let value = if exponent <= self.exponent {
+            pow10(i64::from(self.exponent) - i64::from(exponent)).and_then(|factor| self.mantissa.checked_mul(factor))
+        } else {
+            Some(match pow10(i64::from(exponent) - i64::from(self.exponent)) {
+                Some(divisor) => div_round_up(self.mantissa, divisor),
+                None => self.mantissa.signum(),
+            })
+        };
+        match value {
+            Some(value) if value > i128::from(i64::MAX) => i64::MAX,
+            Some(value) if value < i128::from(i64::MIN) => i64::MIN,
+            Some(value) => value as i64,
+            None if self.mantissa > 0 => i64::MAX,
+            None => i64::MIN,
+        }
+    }
+
+    /// The value rounded up to an integer, saturating at the bounds of `i64`
+    pub fn value(&self) -> i64 {
+        self.scaled_value(0)
+    }
+
+    /// The value in thousandths rounded up, e.g. millicores for CPU
+    pub fn milli_value(&self) -> i64 {
+        self.scaled_value(-3)
+    }
+
+    pub fn as_f64(&self) -> f64 {
+        self.mantissa as f64 * 10f64.powi(self.exponent)
+    }
+
+    pub fn checked_add(&self, other: &Quantity) -> Option<Quantity> {
+        let exponent = self.exponent.min(other.exponent);
+        let mantissa = self.mantissa_at(exponent)?.checked_add(other.mantissa_at(exponent)?)?;
+        // Adding to zero takes the format of the other quantity
+        let format = if self.is_zero() { other.format } else { self.format };
+        Some(Quantity::from_parts(mantissa, exponent, format))
+    }
+
+    pub fn checked_sub(&self, other: &Quantity) -> Option<Quantity> {
+        let negated = Quantity { mantissa: other.mantissa.checked_neg()?, ..*other };
+        self.checked_add(&negated)
+    }
+
+    /// Like `checked_add`, but a sum that can't be represented exactly keeps only its most
+    /// significant digits, and one beyond the representable range saturates at the largest
+    /// quantity of its sign
+    pub fn saturating_add(&self, other: &Quantity) -> Quantity {
+        if let Some(sum) = self.checked_add(other) {
+            return sum;
+        }
+        let format = if self.is_zero() { other.format } else { self.format };
+        // Add at the smallest exponent that keeps both mantissas within 37 digits, so that their
+        // sum fits as well. Digits of the smaller quantity below that exponent are dropped.
+        let magnitude = |quantity: &Quantity| quantity.mantissa.unsigned_abs().to_string().len() as i64 + i64::from(quantity.exponent);
+        let exponent = (magnitude(self).max(magnitude(other)) - 37)
+            .max(i64::from(self.exponent.min(other.exponent)))
+            .min(i64::from(self.exponent.max(other.exponent)));
+        let exponent = i32::try_from(exponent).expect("exponent is between those of the quantities");
+        let truncated = |quantity: &Quantity| if exponent <= quantity.exponent {
+            quantity.mantissa_at(exponent)
+        } else {
+            Some(pow10(i64::from(exponent) - i64::from(quantity.exponent)).map_or(0, |divisor| quantity.mantissa / divisor))
+        };
+        match (truncated(self), truncated(other)) {
+            (Some(left), Some(right)) => match left.checked_add(right) {
+                Some(mantissa) => Quantity::from_parts(mantissa, exponent, format),
+                None => Quantity::saturated(left.signum(), format),
+            },
+            // Only possible at the largest exponent, the sum is out of range
+            _ => {
+                let larger = if self.cmp_magnitude(other) == Ordering::Less { other } else { self };
+                Quantity::saturated(larger.mantissa.signum(), format)
+            },
+        }
+    }
+
+    /// Like `checked_sub`, but rounding and saturating like `saturating_add`
+    pub fn saturating_sub(&self, other: &Quantity) -> Quantity {
+        match self.checked_sub(other) {
+            Some(difference) => difference,
+            None => self.saturating_add(&Quantity { mantissa: other.mantissa.saturating_neg(), ..*other }),
+        }
+    }
+
+    // The largest quantity with the given sign, symmetric so that it can be negated
+    fn saturated(sign: i128, format: QuantityFormat) -> Quantity {
+        let mantissa = if sign < 0 { -i128::MAX } else { i128::MAX };
+        Quantity { mantissa, exponent: i32::MAX, format }
+    }
+
+    // Compare the absolute values by their order of magnitude, then digit by digit
+    fn cmp_magnitude(&self, other: &Quantity) -> Ordering {
+        let left = self.mantissa.unsigned_abs().to_string();
+        let right = other.mantissa.unsigned_abs().to_string();
+        let left_magnitude = left.len() as i64 + i64::from(self.exponent);
+        let right_magnitude = right.len() as i64 + i64::from(other.exponent);
+        left_magnitude.cmp(&right_magnitude).then_with(|| {
+            let width = left.len().max(right.len());
+            format!("{:0<width$}", left, width = width).cmp(&format!("{:0<width$}", right, width = width))
+        })
+    }
+
+    fn write_decimal(&self, f: &mut fmt::Formatter, exponent_suffix: bool) -> fmt::Result {
+        // Use the largest exponent that is a multiple of 3 and keeps the mantissa an integer
+        let mut exponent = self.exponent - self.exponent.rem_euclid(3);
+        if !exponent_suffix {
+            exponent = exponent.min(MAX_SI_EXPONENT);
+        }
+        let mantissa = match self.mantissa_at(exponent) {
+            Some(mantissa) => mantissa,
+            None => return write!(f, "{}e{}", self.mantissa, self.exponent),
+        };
+        if exponent_suffix {
+            if exponent == 0 {
+                write!(f, "{}", mantissa)
+            } else {
+                write!(f, "{}e{}", mantissa, exponent)
+            }
+        } else {
+            let suffix = DECIMAL_SUFFIXES.iter()
+                .find(|&&(_, suffix_exponent)| suffix_exponent == exponent)
+                .map_or("", |&(suffix, _)| suffix);
+            write!(f, "{}{}", mantissa, suffix)
+        }
+    }
+}
+
+impl Default for Quantity {
+    fn default() -> Quantity {
+        Quantity::zero()
+    }
+}
+
+impl FromStr for Quantity {
+    type Err = QuantityParseError;
+
+    fn from_str(input: &str) -> Result<Quantity, QuantityParseError> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err(QuantityParseError::Empty);
+        }
+        let (negative, unsigned) = match input.as_bytes()[0] {
+            b'-' => (true, &input[1..]),
+            b'+' => (false, &input[1..]),
+            _ => (false, input),
+        };
+        let number_len = unsigned.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(unsigned.len());
+        let (number, suffix) = unsigned.split_at(number_len);
+
+        let mut parts = number.splitn(2, '.');
+        let whole = parts.next().unwrap_or("");
+        let fraction = parts.next().unwrap_or("");
+        if (whole.is_empty() && fraction.is_empty()) || fraction.contains('.') {
+            return Err(QuantityParseError::InvalidNumber);
+        }
+        let mut mantissa: i128 = 0;
+        for digit in whole.bytes().chain(fraction.bytes()) {
+            mantissa = mantissa.checked_mul(10)
+                .and_then(|mantissa| mantissa.checked_add(i128::from(digit - b'0')))
+                .ok_or(QuantityParseError::OutOfRange)?;
+        }
+        if negative {
+            mantissa = -mantissa;
+        }
+        let mut exponent = -i32::try_from(fraction.len()).map_err(|_| QuantityParseError::OutOfRange)?;
+
+        let format = if let Some(&(_, shift)) = BINARY_SUFFIXES.iter().find(|&&(name, _)| !name.is_empty() && name == suffix) {
+            mantissa = mantissa.checked_mul(1i128 << shift).ok_or(QuantityParseError::OutOfRange)?;
+            QuantityFormat::BinarySI
+        } else if let Some(&(_, suffix_exponent)) = DECIMAL_SUFFIXES.iter().find(|&&(name, _)| name == suffix) {
+            exponent = exponent.checked_add(suffix_exponent).ok_or(QuantityParseError::OutOfRange)?;
+            QuantityFormat::DecimalSI
+        } else if suffix.len() > 1 && (suffix.starts_with('e') || suffix.starts_with('E')) {
+            let suffix_exponent: i32 = suffix[1..].parse()
+                .map_err(|_| QuantityParseError::InvalidSuffix(suffix.to_string()))?;
+            exponent = exponent.checked_add(suffix_exponent).ok_or(QuantityParseError::OutOfRange)?;
+            QuantityFormat::DecimalExponent
+        } else {
+            return Err(QuantityParseError::InvalidSuffix(suffix.to_string()));
+        };
+        Ok(Quantity::from_parts(mantissa, exponent, format))
+    }
+}
+
+impl fmt::Display for Quantity {
+    /// Writes the canonical form, e.g. `1.5Gi` is written as `1536Mi` and `0.5` as `500m`
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+        match self.format {
+            QuantityFormat::DecimalExponent => self.write_decimal(f, true),
+            QuantityFormat::DecimalSI => self.write_decimal(f, false),
+            QuantityFormat::BinarySI => {
+                // Small values and fractions are written as decimal SI to avoid losing precision
+                let value = match self.mantissa_at(0) {
+                    Some(value) if value <= -1024 || value >= 1024 => value,
+                    _ => return self.write_decimal(f, false),
+                };
+                let &(suffix, shift) = BINARY_SUFFIXES.iter().rev()
+                    .find(|&&(_, shift)| value % (1i128 << shift) == 0)
+                    .unwrap_or(&BINARY_SUFFIXES[0]);
+                write!(f, "{}{}", value >> shift, suffix)
+            },
+        }
+    }
+}
+
+impl PartialEq for Quantity {
+    fn eq(&self, other: &Quantity) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Quantity {}
+
+impl PartialOrd for Quantity {
+    fn partial_cmp(&self, other: &Quantity) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Quantity {
+    fn cmp(&self, other: &Quantity) -> Ordering {
+        match self.mantissa.signum().cmp(&other.mantissa.signum()) {
+            Ordering::Equal => {},
+            ordering => return ordering,
+        }
+        let exponent = self.exponent.min(other.exponent);
+        if let (Some(left), Some(right)) = (self.mantissa_at(exponent), other.mantissa_at(exponent)) {
+            return left.cmp(&right);
+        }
+        // The values are too far apart to line up in an `i128`, compare their digits instead
+        let ordering = self.cmp_magnitude(other);
+        if self.mantissa < 0 {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    }
+}
+
+impl Add for Quantity {
+    type Output = Quantity;
+
+    /// Rounds or saturates a result that can't be represented, see `saturating_add`
+    fn add(self, other: Quantity) -> Quantity {
+        self.saturating_add(&other)
+    }
+}
+
+impl Sub for Quantity {
+    type Output = Quantity;
+
+    /// Rounds or saturates a result that can't be represented, see `saturating_sub`
+    fn sub(self, other: Quantity) -> Quantity {
+        self.saturating_sub(&other)
+    }
+}
+
+impl AddAssign for Quantity {
+    fn add_assign(&mut self, other: Quantity) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Quantity {
+    fn sub_assign(&mut self, other: Quantity) {
+        *self = *self - other;
+    }
+}
+
+impl Sum for Quantity {
+    fn sum<I: Iterator<Item = Quantity>>(iter: I) -> Quantity {
+        iter.fold(Quantity::zero(), Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Quantity> for Quantity {
+    fn sum<I: Iterator<Item = &'a Quantity>>(iter: I) -> Quantity {
+        iter.fold(Quantity::zero(), |sum, quantity| sum + *quantity)
+    }
+}
+
+impl Serialize for Quantity {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+struct QuantityVisitor;
+
+impl<'de> Visitor<'de> for QuantityVisitor {
+    type Value = Quantity;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a quantity, e.g. \"500m\" or \"1Gi\"")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Quantity, E> {
+        value.parse().map_err(E::custom)
+    }
+
+    // Numbers are accepted too, as they are in manifests
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Quantity, E> {
+        Ok(Quantity::new(value, QuantityFormat::DecimalSI))
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Quantity, E> {
+        Ok(Quantity::from_parts(i128::from(value), 0, QuantityFormat::DecimalSI))
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<Quantity, E> {
+        value.to_string().parse().map_err(E::custom)
+    }
+}
+
+impl<'de> Deserialize<'de> for Quantity {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Quantity, D::Error> {
+        deserializer.deserialize_any(QuantityVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quantity(input: &str) -> Quantity {
+        input.parse().unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(quantity("500m").milli_value(), 500);
+        assert_eq!(quantity("1Gi").value(), 1 << 30);
+        assert_eq!(quantity("-1.5k").value(), -1500);
+        assert_eq!(quantity("12e6").value(), 12_000_000);
+        assert_eq!(quantity("0.5").format(), QuantityFormat::DecimalSI);
+        assert_eq!(quantity("1Mi").format(), QuantityFormat::BinarySI);
+        assert_eq!(quantity("1E3").format(), QuantityFormat::DecimalExponent);
+
+        assert_eq!("".parse::<Quantity>(), Err(QuantityParseError::Empty));
+        assert_eq!(".".parse::<Quantity>(), Err(QuantityParseError::InvalidNumber));
+        assert_eq!("1Qi".parse::<Quantity>(), Err(QuantityParseError::InvalidSuffix("Qi".to_string())));
+        assert_eq!("1e".parse::<Quantity>(), Err(QuantityParseError::InvalidSuffix("e".to_string())));
+        assert_eq!("1000000000000000000000000000000000000000".parse::<Quantity>(), Err(QuantityParseError::OutOfRange));
+        assert_eq!(quantity("1.5e2147483647"), quantity("15e2147483646"));
+        assert_eq!("0.1e-2147483648".parse::<Quantity>(), Err(QuantityParseError::OutOfRange));
+    }
+
+    #[test]
+    fn extreme_exponents() {
+        // Used to overflow while normalizing and lining up the exponents
+        let huge = quantity("10e2147483647");
+        let tiny = Quantity::new_scaled(1, i32::MIN);
+        assert_eq!(tiny.to_string(), "1n");
+        assert!(huge > tiny);
+        assert!(huge > quantity("1e30"));
+        assert_eq!(huge.value(), i64::MAX);
+        assert_eq!(huge.milli_value(), i64::MAX);
+        assert_eq!(tiny.milli_value(), 1);
+    }
+
+    #[test]
+    fn canonical_form() {
+        assert_eq!(quantity("1.5Gi").to_string(), "1536Mi");
+        assert_eq!(quantity("0.5").to_string(), "500m");
+        assert_eq!(quantity("1000").to_string(), "1k");
+        assert_eq!(quantity("1500").to_string(), "1500");
+        assert_eq!(quantity("12e6").to_string(), "12e6");
+        assert_eq!(quantity("100Ki").to_string(), "100Ki");
+        assert_eq!(quantity("512").to_string(), "512");
+        assert_eq!(quantity("0.1n").to_string(), "1n");
+        assert_eq!(quantity("-0").to_string(), "0");
+        assert_eq!(quantity("1e30").to_string(), "1e30");
+        assert_eq!(quantity("1000E").to_string(), "1000E");
+    }
+
+    #[test]
+    fn comparison() {
+        assert_eq!(quantity("1Ki"), quantity("1024"));
+        assert_eq!(quantity("1"), quantity("1000m"));
+        assert!(quantity("500m") < quantity("1"));
+        assert!(quantity("-1") < quantity("1n"));
+        assert!(quantity("-1e30") < quantity("-1n"));
+        assert!(quantity("1e30") > quantity("1n"));
+        assert!(quantity("12e30") > quantity("11999999999999999999999999999999999999e-7"));
+        assert!(quantity("-12e30") < quantity("-11999999999999999999999999999999999999e-7"));
+    }
+
+    #[test]
+    fn arithmetic() {
+        assert_eq!((quantity("1Gi") + quantity("512Mi")).to_string(), "1536Mi");
+        assert_eq!((quantity("1") - quantity("250m")).to_string(), "750m");
+        assert_eq!((Quantity::zero() + quantity("1Mi")).format(), QuantityFormat::BinarySI);
+        let total: Quantity = ["100m", "200m", "700m"].iter().map(|input| quantity(input)).sum();
+        assert_eq!(total, quantity("1"));
+        assert_eq!(quantity("1e30").checked_add(&quantity("1n")), None);
+        let mut value = quantity("1");
+        value -= quantity("2");
+        assert_eq!(value, quantity("-1"));
+    }
+
+    #[test]
+    fn overflow() {
+        // Too precise to be exact, the nanos are dropped
+        assert_eq!(quantity("1e40") + quantity("1n"), quantity("1e40"));
+        assert_eq!(quantity("1e40") - quantity("1n"), quantity("1e40"));
+        let sum = quantity("123456789e30") + quantity("987654321");
+        assert!(sum > quantity("123456789e30"));
+        assert!(sum < quantity("123456790e30"));
+        // Out of range, saturated
+        let max = Quantity::saturated(1, QuantityFormat::DecimalExponent);
+        let min = Quantity::saturated(-1, QuantityFormat::DecimalExponent);
+        assert_eq!(max + max, max);
+        assert_eq!(max + quantity("1"), max);
+        assert_eq!(min - max, min);
+        assert_eq!(quantity("-1") - max, min);
+        assert_eq!(max - max, Quantity::zero());
+        let mut value = max;
+        value += quantity("10e2147483647");
+        assert_eq!(value, max);
+    }
+
+    #[test]
+    fn serde() {
+        let quantities: Vec<Quantity> = serde_json::from_str(r#"["1.5Gi", 2, 0.5]"#).unwrap();
+        assert_eq!(serde_json::to_string(&quantities).unwrap(), r#"["1536Mi","2","500m"]"#);
+    }
+}
@@ -0,0 +1,84 @@
+use super::TypeMeta;
+
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct APIVersions {
+    #[serde(flatten)]
+    pub type_meta: TypeMeta,
+    pub versions: Vec<String>,
+    #[serde(rename = "serverAddressByClientCIDRs", default, skip_serializing_if = "Vec::is_empty")]
+    pub server_address_by_client_cidrs: Vec<ServerAddressByClientCIDR>,
+}
+
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct APIGroupList {
+    #[serde(flatten)]
+    pub type_meta: TypeMeta,
+    pub groups: Vec<APIGroup>,
+}
+
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct APIGroup {
+    #[serde(flatten)]
+    pub type_meta: TypeMeta,
+    pub name: String,
+    pub versions: Vec<GroupVersionForDiscovery>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preferred_version: Option<GroupVersionForDiscovery>,
+    #[serde(rename = "serverAddressByClientCIDRs", default, skip_serializing_if = "Vec::is_empty")]
+    pub server_address_by_client_cidrs: Vec<ServerAddressByClientCIDR>,
+}
+
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct GroupVersionForDiscovery {
+    pub group_version: String,
+    pub version: String,
+}
+
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct ServerAddressByClientCIDR {
+    #[serde(rename = "clientCIDR")]
+    pub client_cidr: String,
+    pub server_address: String,
+}
+
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct APIResourceList {
+    #[serde(flatten)]
+    pub type_meta: TypeMeta,
+    pub group_version: String,
+    pub resources: Vec<APIResource>,
+}
+
+#[serde(rename_all = "camelCase")]
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct APIResource {
+    pub name: String,
+    pub singular_name: String,
+    pub namespaced: bool,
+    // Only set for subresources that belong to a different group or version, e.g. `Scale`
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub group: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub version: String,
+    pub kind: String,
+    pub verbs: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub short_names: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub categories: Vec<String>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub storage_version_hash: String,
+}
+
+impl APIResource {
+    /// Subresources are listed alongside their parent resource as `<resource>/<subresource>`
+    pub fn is_subresource(&self) -> bool {
+        self.name.contains('/')
+    }
+}
@@ -3,9 +3,13 @@ use chrono;
 use num_traits::Zero;
 use uuid::Uuid;
 
+use crate::apimachinery::labels::Selector;
+
 mod group_version;
+mod discovery;
 
 pub use self::group_version::*;
+pub use self::discovery::*;
 
 #[serde(rename_all = "camelCase")]
 #[derive(Debug, Serialize, Deserialize, Default)]
@@ -32,7 +36,7 @@ pub struct ObjectMeta {
     // That's apparently Year 1, January 1, 00:00:00.0 UTC
     // We could make a fixed function and skip serialization on that, but the sheer
     // stupidity of a "zero value" for time makes my brain hurt.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
     pub creation_timestamp: Option<Time>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub deletion_timestamp: Option<Time>,
@@ -50,6 +54,8 @@ pub struct ObjectMeta {
     pub finalizers: Vec<String>,
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub cluster_name: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -77,6 +83,8 @@ pub struct OwnerReference {
     pub controller: Option<bool>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub block_owner_deletion: Option<bool>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[serde(rename_all = "camelCase")]
@@ -102,24 +110,79 @@ pub struct TypeMeta {
 
 pub type Time = chrono::DateTime<chrono::FixedOffset>;
 
+/// See `apimachinery::labels::Selector` for parsing, rendering and evaluating selectors
 #[serde(rename_all = "camelCase")]
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
 pub struct LabelSelector {
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub match_labels: HashMap<String, String>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub match_expressions: Vec<LabelSelectorRequirement>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-pub type LabelSelectorOperator = String;
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum LabelSelectorOperator {
+    In,
+    NotIn,
+    Exists,
+    DoesNotExist,
+}
 
 #[serde(rename_all = "camelCase")]
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct LabelSelectorRequirement {
     pub key: String,
     pub operator: LabelSelectorOperator,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub values: Vec<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Query parameters of list requests
+#[serde(rename_all = "camelCase")]
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct ListOptions {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub label_selector: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub field_selector: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub resource_version: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_seconds: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i64>,
+    #[serde(rename = "continue", default, skip_serializing_if = "String::is_empty")]
+    pub continue_: String,
+}
+
+impl ListOptions {
+    pub fn with_selector(selector: &Selector) -> ListOptions {
+        ListOptions {
+            label_selector: selector.to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+/// The standard condition type of the status of objects
+#[serde(rename_all = "camelCase")]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Condition {
+    #[serde(rename = "type")]
+    pub condition_type: String,
+    /// `True`, `False` or `Unknown`
+    pub status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub observed_generation: Option<i64>,
+    pub last_transition_time: Time,
+    pub reason: String,
+    pub message: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 type CauseType = String;
@@ -172,3 +235,13 @@ pub struct Status {
     #[serde(default)]
     pub code: i32,
 }
+
+/// An event sent by the API server for watched objects
+#[serde(tag = "type", content = "object", rename_all = "UPPERCASE")]
+#[derive(Debug, Serialize, Deserialize)]
+pub enum WatchEvent<T> {
+    Added(T),
+    Modified(T),
+    Deleted(T),
+    Error(Status),
+}
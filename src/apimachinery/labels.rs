@@ -0,0 +1,374 @@
+//! Label selectors, parsed from and rendered to the syntax used by `kubectl` and the
+//! `labelSelector` query parameter, e.g. `app=web,tier notin (db),!canary`.
+use std::collections::HashMap;
+use std::error::Error as StdError;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::apimachinery::apis::meta::v1::{LabelSelector, LabelSelectorOperator, LabelSelectorRequirement};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Equals,
+    NotEquals,
+    In,
+    NotIn,
+    Exists,
+    DoesNotExist,
+}
+
+/// A single condition on the labels of an object
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Requirement {
+    pub key: String,
+    pub operator: Operator,
+    /// One value for `Equals` and `NotEquals`, at least one for `In` and `NotIn`, none otherwise
+    pub values: Vec<String>,
+}
+
+impl Requirement {
+    pub fn matches(&self, labels: &HashMap<String, String>) -> bool {
+        let value = labels.get(&self.key);
+        match self.operator {
+            Operator::Exists => value.is_some(),
+            Operator::DoesNotExist => value.is_none(),
+            Operator::Equals | Operator::In => matches!(value, Some(value) if self.values.contains(value)),
+            Operator::NotEquals | Operator::NotIn => !matches!(value, Some(value) if self.values.contains(value)),
+        }
+    }
+}
+
+impl fmt::Display for Requirement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.operator {
+            Operator::Exists => write!(f, "{}", self.key),
+            Operator::DoesNotExist => write!(f, "!{}", self.key),
+            Operator::Equals => write!(f, "{}={}", self.key, self.values.join("")),
+            Operator::NotEquals => write!(f, "{}!={}", self.key, self.values.join("")),
+            Operator::In => write!(f, "{} in ({})", self.key, self.values.join(",")),
+            Operator::NotIn => write!(f, "{} notin ({})", self.key, self.values.join(",")),
+        }
+    }
+}
+
+/// A set of requirements that all have to match. The empty selector matches everything.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Selector {
+    pub requirements: Vec<Requirement>,
+}
+
+impl Selector {
+    pub fn new() -> Selector {
+        Selector::default()
+    }
+
+    fn require(mut self, key: &str, operator: Operator, values: Vec<String>) -> Selector {
+        self.requirements.push(Requirement { key: key.to_string(), operator, values });
+        self
+    }
+
+    pub fn equals(self, key: &str, value: &str) -> Selector {
+        self.require(key, Operator::Equals, vec![value.to_string()])
+    }
+
+    pub fn not_equals(self, key: &str, value: &str) -> Selector {
+        self.require(key, Operator::NotEquals, vec![value.to_string()])
+    }
+
+    pub fn in_values(self, key: &str, values: &[&str]) -> Selector {
+        self.require(key, Operator::In, values.iter().map(|value| value.to_string()).collect())
+    }
+
+    pub fn not_in(self, key: &str, values: &[&str]) -> Selector {
+        self.require(key, Operator::NotIn, values.iter().map(|value| value.to_string()).collect())
+    }
+
+    pub fn exists(self, key: &str) -> Selector {
+        self.require(key, Operator::Exists, Vec::new())
+    }
+
+    pub fn does_not_exist(self, key: &str) -> Selector {
+        self.require(key, Operator::DoesNotExist, Vec::new())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.requirements.is_empty()
+    }
+
+    /// Evaluate the selector against labels, e.g. `ObjectMeta::labels`
+    pub fn matches(&self, labels: &HashMap<String, String>) -> bool {
+        self.requirements.iter().all(|requirement| requirement.matches(labels))
+    }
+}
+
+impl fmt::Display for Selector {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, requirement) in self.requirements.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{}", requirement)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SelectorParseError {
+    UnexpectedToken(String),
+    UnexpectedEnd,
+    EmptyKey,
+    /// `in` and `notin` need at least one value
+    EmptyValues(String),
+}
+
+impl fmt::Display for SelectorParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SelectorParseError::UnexpectedToken(token) => write!(f, "Unexpected token `{}` in label selector", token),
+            SelectorParseError::UnexpectedEnd => write!(f, "Unexpected end of label selector"),
+            SelectorParseError::EmptyKey => write!(f, "Empty key in label selector"),
+            SelectorParseError::EmptyValues(key) => write!(f, "No values given for `{}` in label selector", key),
+        }
+    }
+}
+
+impl StdError for SelectorParseError {}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Token {
+    Identifier(String),
+    Equals,
+    DoubleEquals,
+    NotEquals,
+    Not,
+    Comma,
+    Open,
+    Close,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Token::Identifier(identifier) => write!(f, "{}", identifier),
+            Token::Equals => write!(f, "="),
+            Token::DoubleEquals => write!(f, "=="),
+            Token::NotEquals => write!(f, "!="),
+            Token::Not => write!(f, "!"),
+            Token::Comma => write!(f, ","),
+            Token::Open => write!(f, "("),
+            Token::Close => write!(f, ")"),
+        }
+    }
+}
+
+fn is_special(c: char) -> bool {
+    c.is_whitespace() || "=!,()".contains(c)
+}
+
+fn tokenize(input: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => { chars.next(); },
+            '=' | '!' => {
+                chars.next();
+                let double = chars.peek() == Some(&'=');
+                if double {
+                    chars.next();
+                }
+                tokens.push(match (c, double) {
+                    ('=', false) => Token::Equals,
+                    ('=', true) => Token::DoubleEquals,
+                    ('!', false) => Token::Not,
+                    _ => Token::NotEquals,
+                });
+            },
+            ',' => { chars.next(); tokens.push(Token::Comma); },
+            '(' => { chars.next(); tokens.push(Token::Open); },
+            ')' => { chars.next(); tokens.push(Token::Close); },
+            _ => {
+                let mut identifier = String::new();
+                while let Some(&c) = chars.peek() {
+                    if is_special(c) {
+                        break;
+                    }
+                    identifier.push(c);
+                    chars.next();
+                }
+                tokens.push(Token::Identifier(identifier));
+            },
+        }
+    }
+    tokens
+}
+
+struct Parser {
+    tokens: ::std::iter::Peekable<::std::vec::IntoIter<Token>>,
+}
+
+impl Parser {
+    fn next(&mut self) -> Result<Token, SelectorParseError> {
+        self.tokens.next().ok_or(SelectorParseError::UnexpectedEnd)
+    }
+
+    fn key(&mut self) -> Result<String, SelectorParseError> {
+        match self.next()? {
+            Token::Identifier(key) => Ok(key),
+            Token::Comma | Token::Equals | Token::DoubleEquals | Token::NotEquals => Err(SelectorParseError::EmptyKey),
+            token => Err(SelectorParseError::UnexpectedToken(token.to_string())),
+        }
+    }
+
+    // A single value, which may be empty, e.g. `key=`
+    fn value(&mut self) -> String {
+        if let Some(Token::Identifier(_)) = self.tokens.peek() {
+            if let Some(Token::Identifier(value)) = self.tokens.next() {
+                return value;
+            }
+        }
+        String::new()
+    }
+
+    // A parenthesized, comma separated set of values
+    fn values(&mut self, key: &str) -> Result<Vec<String>, SelectorParseError> {
+        match self.next()? {
+            Token::Open => (),
+            token => return Err(SelectorParseError::UnexpectedToken(token.to_string())),
+        }
+        let mut values = Vec::new();
+        loop {
+            let value = self.value();
+            match self.next()? {
+                Token::Comma => values.push(value),
+                Token::Close => {
+                    if !value.is_empty() || !values.is_empty() {
+                        values.push(value);
+                    }
+                    break;
+                },
+                token => return Err(SelectorParseError::UnexpectedToken(token.to_string())),
+            }
+        }
+        if values.is_empty() {
+            return Err(SelectorParseError::EmptyValues(key.to_string()));
+        }
+        Ok(values)
+    }
+
+    fn requirement(&mut self) -> Result<Requirement, SelectorParseError> {
+        if self.tokens.peek() == Some(&Token::Not) {
+            self.tokens.next();
+            let key = self.key()?;
+            return Ok(Requirement { key, operator: Operator::DoesNotExist, values: Vec::new() });
+        }
+        let key = self.key()?;
+        let (operator, values) = match self.tokens.peek() {
+            None | Some(Token::Comma) => (Operator::Exists, Vec::new()),
+            Some(Token::Equals) | Some(Token::DoubleEquals) => {
+                self.tokens.next();
+                (Operator::Equals, vec![self.value()])
+            },
+            Some(Token::NotEquals) => {
+                self.tokens.next();
+                (Operator::NotEquals, vec![self.value()])
+            },
+            Some(Token::Identifier(ref operator)) if operator == "in" => {
+                self.tokens.next();
+                (Operator::In, self.values(&key)?)
+            },
+            Some(Token::Identifier(ref operator)) if operator == "notin" => {
+                self.tokens.next();
+                (Operator::NotIn, self.values(&key)?)
+            },
+            Some(_) => return Err(SelectorParseError::UnexpectedToken(self.next()?.to_string())),
+        };
+        Ok(Requirement { key, operator, values })
+    }
+}
+
+impl FromStr for Selector {
+    type Err = SelectorParseError;
+
+    fn from_str(input: &str) -> Result<Selector, SelectorParseError> {
+        let mut parser = Parser { tokens: tokenize(input).into_iter().peekable() };
+        let mut selector = Selector::new();
+        if parser.tokens.peek().is_none() {
+            return Ok(selector);
+        }
+        loop {
+            selector.requirements.push(parser.requirement()?);
+            match parser.tokens.next() {
+                None => return Ok(selector),
+                Some(Token::Comma) => (),
+                Some(token) => return Err(SelectorParseError::UnexpectedToken(token.to_string())),
+            }
+        }
+    }
+}
+
+impl From<&LabelSelector> for Selector {
+    fn from(selector: &LabelSelector) -> Selector {
+        // Sorted so the result doesn't depend on the order of the map
+        let mut match_labels: Vec<_> = selector.match_labels.iter().collect();
+        match_labels.sort();
+        let mut requirements: Vec<Requirement> = match_labels.into_iter()
+            .map(|(key, value)| Requirement {
+                key: key.clone(),
+                operator: Operator::Equals,
+                values: vec![value.clone()],
+            })
+            .collect();
+        requirements.extend(selector.match_expressions.iter().map(|expression| Requirement {
+            key: expression.key.clone(),
+            operator: match expression.operator {
+                LabelSelectorOperator::In => Operator::In,
+                LabelSelectorOperator::NotIn => Operator::NotIn,
+                LabelSelectorOperator::Exists => Operator::Exists,
+                LabelSelectorOperator::DoesNotExist => Operator::DoesNotExist,
+            },
+            values: expression.values.clone(),
+        }));
+        Selector { requirements }
+    }
+}
+
+impl From<LabelSelector> for Selector {
+    fn from(selector: LabelSelector) -> Selector {
+        Selector::from(&selector)
+    }
+}
+
+impl From<Selector> for LabelSelector {
+    /// `NotEquals` has no equivalent in `LabelSelector` and becomes `NotIn` with a single value
+    fn from(selector: Selector) -> LabelSelector {
+        let mut label_selector = LabelSelector::default();
+        for requirement in selector.requirements {
+            let operator = match requirement.operator {
+                Operator::Equals => {
+                    let value = requirement.values.into_iter().next().unwrap_or_default();
+                    label_selector.match_labels.insert(requirement.key, value);
+                    continue;
+                },
+                Operator::NotEquals | Operator::NotIn => LabelSelectorOperator::NotIn,
+                Operator::In => LabelSelectorOperator::In,
+                Operator::Exists => LabelSelectorOperator::Exists,
+                Operator::DoesNotExist => LabelSelectorOperator::DoesNotExist,
+            };
+            label_selector.match_expressions.push(LabelSelectorRequirement {
+                key: requirement.key,
+                operator,
+                values: requirement.values,
+                extra: Default::default(),
+            });
+        }
+        label_selector
+    }
+}
+
+impl LabelSelector {
+    pub fn matches(&self, labels: &HashMap<String, String>) -> bool {
+        Selector::from(self).matches(labels)
+    }
+}
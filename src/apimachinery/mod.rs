@@ -1,3 +1,4 @@
 pub mod apis;
 pub mod api;
+pub mod labels;
 pub mod util;
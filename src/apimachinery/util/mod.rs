@@ -1,6 +1,92 @@
+use std::convert::TryFrom;
+use std::error::Error as StdError;
+use std::fmt;
+
 #[serde(untagged)]
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub enum IntOrString {
     Int(i64),
     String(String),
 }
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum IntOrStringError {
+    /// The value is a string that doesn't end in `%`
+    NotAPercentage(String),
+    /// The value ends in `%` but isn't a non-negative integer percentage
+    InvalidPercentage(String),
+    /// The scaled percentage doesn't fit in an `i64`
+    OutOfRange(String),
+}
+
+impl fmt::Display for IntOrStringError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IntOrStringError::NotAPercentage(value) => write!(f, "Invalid value for IntOrString, `{}` is not a percentage", value),
+            IntOrStringError::InvalidPercentage(value) => write!(f, "Invalid percentage `{}`", value),
+            IntOrStringError::OutOfRange(value) => write!(f, "Scaled value of `{}` is out of range", value),
+        }
+    }
+}
+
+impl StdError for IntOrStringError {}
+
+impl IntOrString {
+    pub fn is_percent(&self) -> bool {
+        match self {
+            IntOrString::Int(_) => false,
+            IntOrString::String(value) => value.ends_with('%'),
+        }
+    }
+
+    /// The percentage of the string form, which has to be a non-negative integer followed by `%`
+    pub fn percent(&self) -> Result<i64, IntOrStringError> {
+        let value = match self {
+            IntOrString::Int(value) => return Err(IntOrStringError::NotAPercentage(value.to_string())),
+            IntOrString::String(value) => value,
+        };
+        let digits = match value.strip_suffix('%') {
+            Some(digits) => digits,
+            None => return Err(IntOrStringError::NotAPercentage(value.clone())),
+        };
+        if digits.is_empty() || !digits.bytes().all(|digit| digit.is_ascii_digit()) {
+            return Err(IntOrStringError::InvalidPercentage(value.clone()));
+        }
+        digits.parse().map_err(|_| IntOrStringError::InvalidPercentage(value.clone()))
+    }
+
+    /// Resolve the value against `total`: integers are returned as-is and percentages are scaled,
+    /// rounding up or down. Matches `GetScaledValueFromIntOrPercent` in Kubernetes, e.g. the
+    /// `maxSurge` of a deployment is rounded up while `maxUnavailable` is rounded down.
+    pub fn get_scaled_value(&self, total: i64, round_up: bool) -> Result<i64, IntOrStringError> {
+        match self {
+            IntOrString::Int(value) => Ok(*value),
+            IntOrString::String(percentage) => {
+                let scaled = i128::from(self.percent()?) * i128::from(total);
+                let value = if round_up {
+                    (scaled + 99).div_euclid(100)
+                } else {
+                    scaled.div_euclid(100)
+                };
+                i64::try_from(value).map_err(|_| IntOrStringError::OutOfRange(percentage.clone()))
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scaled_value() {
+        let percentage = IntOrString::String("25%".to_string());
+        assert_eq!(percentage.get_scaled_value(10, true), Ok(3));
+        assert_eq!(percentage.get_scaled_value(10, false), Ok(2));
+        assert_eq!(IntOrString::Int(7).get_scaled_value(10, true), Ok(7));
+        assert_eq!(IntOrString::String("25".to_string()).get_scaled_value(10, true),
+                   Err(IntOrStringError::NotAPercentage("25".to_string())));
+        let huge = IntOrString::String("200%".to_string());
+        assert_eq!(huge.get_scaled_value(i64::MAX, false), Err(IntOrStringError::OutOfRange("200%".to_string())));
+    }
+}
@@ -1,11 +1,14 @@
-use std::{env, fmt};
+use std::{env, fmt, fs, thread};
 use std::error::Error as StdError;
 use std::io::Error as IoError;
+use std::path::Path;
+use std::time::{Duration, Instant, SystemTime};
 
 use serde_json;
 use serde::Serialize;
 use serde::de::DeserializeOwned;
-use serde_json::Error as JsonError;
+use serde_json::{Deserializer, Error as JsonError, Value};
+use serde_json::de::{IoRead, StreamDeserializer};
 use reqwest::{
     Method,
     Certificate,
@@ -13,25 +16,66 @@ use reqwest::{
     Result as HttpResult,
     Response,
     Error as HttpError,
+    Proxy,
     RequestBuilder,
+    StatusCode,
+    Url,
 };
-use reqwest::header::{AUTHORIZATION, CONTENT_TYPE};
+use reqwest::header::{AUTHORIZATION, CONTENT_TYPE, RETRY_AFTER, USER_AGENT, HeaderMap, HeaderValue};
 
 use crate::utils;
-use crate::api::KubeKind;
-use crate::config::{ClientConfig, AuthConfig};
+use crate::api::{Cluster, KubeKind, Namespaced};
+use crate::api::apiextensions::v1::CustomResourceDefinition;
+use crate::api::authentication::v1::{TokenRequest, TokenReview, TokenReviewStatus};
+use crate::api::authorization::v1::{
+    ResourceAttributes,
+    SelfSubjectAccessReview,
+    SelfSubjectAccessReviewSpec,
+    SelfSubjectRulesReview,
+    SubjectAccessReview,
+    SubjectAccessReviewSpec,
+    SubjectAccessReviewStatus,
+    SubjectRulesReviewStatus,
+};
+use crate::api::core::v1::ServiceAccount;
+use crate::api::dynamic::{ApiResource, DynamicObject, DynamicObjectList};
+use crate::apimachinery::apis::meta::v1::{
+    APIGroup,
+    APIGroupList,
+    APIVersions,
+    GroupVersionForDiscovery,
+    ListOptions,
+    Status,
+    WatchEvent,
+};
+use crate::discovery::{Discovery, GroupDiscoveryFailure};
+use crate::config::{ClientConfig, AuthConfig, Impersonation, TokenFile, KubeconfigParseError, kubeconfig};
+use crate::ratelimit::RateLimiter;
+use crate::retry::{Backoff, RetryPolicy};
 
 // the name and location for in-cluster runtime configuration parameters
 const INCLUSTER_CA_FILE: &str = "/var/run/secrets/kubernetes.io/serviceaccount/ca.crt";
 const INCLUSTER_TOKEN_FILE: &str = "/var/run/secrets/kubernetes.io/serviceaccount/token";
+const INCLUSTER_NAMESPACE_FILE: &str = "/var/run/secrets/kubernetes.io/serviceaccount/namespace";
 const INCLUSTER_API_HOST_NAME: &str = "KUBERNETES_SERVICE_HOST";
 const INCLUSTER_API_PORT_NAME: &str = "KUBERNETES_SERVICE_PORT";
+const INCLUSTER_API_HTTPS_PORT_NAME: &str = "KUBERNETES_SERVICE_PORT_HTTPS";
+const DEFAULT_NAMESPACE: &str = "default";
+
+// How often to check whether a CRD has been established
+const ESTABLISHED_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+const DEFAULT_USER_AGENT: &str = concat!("kubeclient/", env!("CARGO_PKG_VERSION"));
 
 #[derive(Clone)]
 pub struct KubeClient {
     auth_info: AuthConfig,
     api_url: String,
     client: Client,
+    retry_policy: RetryPolicy,
+    rate_limiter: Option<RateLimiter>,
+    impersonation: Option<Impersonation>,
+    default_namespace: String,
 }
 
 // produce a base HTTP URI from the given host and port
@@ -53,10 +97,28 @@ pub enum RequestError {
     HttpError(Response),
     /// The response could not be deserialized
     SerdeError(JsonError),
+    /// The operation did not finish in time
+    Timeout,
     /// Other misc. error
     MiscError,
 }
 
+impl RequestError {
+    /// The HTTP status of the response, if the API server responded with an error
+    pub fn status_code(&self) -> Option<StatusCode> {
+        match self {
+            RequestError::HttpError(response) => Some(response.status()),
+            _ => None,
+        }
+    }
+
+    /// Whether the API server responded with 409 Conflict, e.g. because the object already
+    /// exists or was modified since it was read
+    pub fn is_conflict(&self) -> bool {
+        self.status_code() == Some(StatusCode::CONFLICT)
+    }
+}
+
 impl fmt::Display for RequestError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", self.description())
@@ -72,6 +134,8 @@ impl StdError for RequestError {
                 "Received an unexpected response from the Kubernetes API",
             RequestError::SerdeError(_) =>
                 "Error during deserialization of response body",
+            RequestError::Timeout =>
+                "The operation timed out",
             RequestError::MiscError =>
                 "Unknown, miscellaneous error (shouldn't happen)",
         }
@@ -81,6 +145,7 @@ impl StdError for RequestError {
             RequestError::TransportError(error) => Some(error as &StdError),
             RequestError::HttpError(_) => None,
             RequestError::SerdeError(error) => Some(error as &StdError),
+            RequestError::Timeout => None,
             RequestError::MiscError => None,
         }
     }
@@ -88,6 +153,42 @@ impl StdError for RequestError {
 
 pub type RequestResult<T> = Result<T, RequestError>;
 
+/// The kind of patch sent in PATCH requests
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatchStrategy {
+    /// Strategic merge patch, only supported by built-in types
+    StrategicMerge,
+    /// JSON merge patch (RFC 7386)
+    Merge,
+    /// JSON Patch (RFC 6902)
+    Json,
+}
+
+impl PatchStrategy {
+    fn content_type(self) -> &'static str {
+        match self {
+            PatchStrategy::StrategicMerge => "application/strategic-merge-patch+json",
+            PatchStrategy::Merge => "application/merge-patch+json",
+            PatchStrategy::Json => "application/json-patch+json",
+        }
+    }
+}
+
+/// Iterator over the events of a watch. The iterator ends when the API server closes the
+/// connection, or when no event arrives within the read timeout if one was set with
+/// `KubeClientBuilder::read_timeout`.
+pub struct WatchEvents<T> {
+    events: StreamDeserializer<'static, IoRead<Response>, WatchEvent<T>>,
+}
+
+impl<T: DeserializeOwned> Iterator for WatchEvents<T> {
+    type Item = RequestResult<WatchEvent<T>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.events.next().map(|event| event.map_err(RequestError::SerdeError))
+    }
+}
+
 /// Errors that can occur during client initialization
 #[derive(Debug)]
 pub enum ClientInitError {
@@ -99,44 +200,263 @@ pub enum ClientInitError {
     InvalidCert(HttpError),
     /// Low-level HTTP client-building error
     ClientBuildingError(HttpError),
+    /// The auth method of the config isn't supported yet
+    UnsupportedAuth(AuthConfig),
+    /// The certificate of the API server can't be verified for a name other than the host of its
+    /// URL, see `KubeClientBuilder::tls_server_name`
+    UnsupportedTlsServerName(String),
 }
 
-impl KubeClient {
-    pub fn new(config: ClientConfig) -> Result<KubeClient, ClientInitError> {
-        let mut builder = Client::builder();
+/// Builder for `KubeClient`s with non-default connection settings
+pub struct KubeClientBuilder {
+    config: ClientConfig,
+    connect_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+    proxy: Option<Proxy>,
+    insecure_skip_tls_verify: bool,
+    tls_server_name: Option<String>,
+    root_certificates: Vec<Certificate>,
+    default_headers: HeaderMap,
+    impersonation: Option<Impersonation>,
+    default_namespace: Option<String>,
+}
+
+impl KubeClientBuilder {
+    pub fn new(config: ClientConfig) -> KubeClientBuilder {
+        let mut default_headers = HeaderMap::new();
+        default_headers.insert(USER_AGENT, HeaderValue::from_static(DEFAULT_USER_AGENT));
+        KubeClientBuilder {
+            config,
+            connect_timeout: None,
+            read_timeout: None,
+            proxy: None,
+            insecure_skip_tls_verify: false,
+            tls_server_name: None,
+            root_certificates: Vec::new(),
+            default_headers,
+            impersonation: None,
+            default_namespace: None,
+        }
+    }
+
+    /// A builder for the given context of a kubeconfig, or its current context. Besides the
+    /// server and credentials this picks up `insecure-skip-tls-verify`, `tls-server-name` and
+    /// `proxy-url` of the cluster, the impersonation settings of the user and the namespace of the
+    /// context. A `tls-server-name` other than the host of the server isn't supported, see
+    /// `tls_server_name`.
+    pub fn from_kubeconfig(config: kubeconfig::Config, context: Option<String>) -> Result<KubeClientBuilder, KubeconfigParseError> {
+        let (insecure_skip_tls_verify, tls_server_name, proxy_url, impersonation, namespace) = {
+            let (context, cluster, user) = kubeconfig::resolve_context(&config, context.clone())?;
+            if let Some(ref tls_server_name) = cluster.tls_server_name {
+                if !is_url_host(&cluster.server, tls_server_name) {
+                    return Err(KubeconfigParseError::UnsupportedTlsServerName(tls_server_name.clone()));
+                }
+            }
+            (cluster.insecure_skip_tls_verify.unwrap_or(false),
+             cluster.tls_server_name.clone(),
+             cluster.proxy_url.clone(),
+             Impersonation::from_kubeconfig_user(user),
+             context.namespace.clone())
+        };
+        let mut builder = KubeClientBuilder::new(ClientConfig::from_kubeconfig(config, context)?)
+            .insecure_skip_tls_verify(insecure_skip_tls_verify);
+        builder.impersonation = impersonation;
+        builder.default_namespace = namespace;
+        if let Some(tls_server_name) = tls_server_name {
+            builder = builder.tls_server_name(tls_server_name);
+        }
+        if let Some(proxy_url) = proxy_url {
+            let proxy = Proxy::all(&proxy_url).map_err(|_| KubeconfigParseError::InvalidProxyUrl(proxy_url))?;
+            builder = builder.proxy(proxy);
+        }
+        Ok(builder)
+    }
+
+    /// Timeout for establishing connections to the API server
+    pub fn connect_timeout(mut self, timeout: Duration) -> KubeClientBuilder {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Timeout for every read from and write to the connection, none by default.
+    ///
+    /// NOTE: this applies to watches as well, which end when no event arrives in time.
+    pub fn read_timeout(mut self, timeout: Option<Duration>) -> KubeClientBuilder {
+        self.read_timeout = timeout;
+        self
+    }
+
+    /// Send requests through an HTTP(S) proxy
+    pub fn proxy(mut self, proxy: Proxy) -> KubeClientBuilder {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// Don't verify the certificate of the API server. Insecure, only for testing.
+    pub fn insecure_skip_tls_verify(mut self, insecure_skip_tls_verify: bool) -> KubeClientBuilder {
+        self.insecure_skip_tls_verify = insecure_skip_tls_verify;
+        self
+    }
+
+    /// Verify the certificate of the API server for this name instead of the host of its URL.
+    ///
+    /// NOTE: the HTTP client can't override the name sent in SNI and verified in the
+    /// certificate, so `build` fails with `ClientInitError::UnsupportedTlsServerName` unless the
+    /// name is the host of the URL anyway.
+    pub fn tls_server_name<S: Into<String>>(mut self, tls_server_name: S) -> KubeClientBuilder {
+        self.tls_server_name = Some(tls_server_name.into());
+        self
+    }
 
-        match config {
+    /// Trust an additional CA besides the one in the config
+    pub fn add_root_certificate(mut self, certificate: Certificate) -> KubeClientBuilder {
+        self.root_certificates.push(certificate);
+        self
+    }
+
+    /// Send this User-Agent instead of `kubeclient/<version>`
+    pub fn user_agent(mut self, user_agent: HeaderValue) -> KubeClientBuilder {
+        self.default_headers.insert(USER_AGENT, user_agent);
+        self
+    }
+
+    /// Send these headers with every request, overriding earlier ones with the same name
+    pub fn default_headers(mut self, headers: HeaderMap) -> KubeClientBuilder {
+        for (name, value) in headers.iter() {
+            self.default_headers.insert(name, value.clone());
+        }
+        self
+    }
+
+    /// Make requests on behalf of another user
+    pub fn impersonate(mut self, impersonation: Impersonation) -> KubeClientBuilder {
+        self.impersonation = Some(impersonation);
+        self
+    }
+
+    /// The namespace returned by `KubeClient::default_namespace`, instead of the one of the
+    /// pod's service account, the kubeconfig context, or "default"
+    pub fn default_namespace<S: Into<String>>(mut self, namespace: S) -> KubeClientBuilder {
+        self.default_namespace = Some(namespace.into());
+        self
+    }
+
+    pub fn build(self) -> Result<KubeClient, ClientInitError> {
+        let mut default_namespace = self.default_namespace;
+        let (api_url, auth_info, ca) = match self.config {
             ClientConfig::InCluster => {
                 let err_mapper = |var_name| |error| ClientInitError::EnvVarError(var_name, error);
                 // TODO: convert ca.crt from PEM to DER
                 let host = env::var(INCLUSTER_API_HOST_NAME).map_err(err_mapper(INCLUSTER_API_HOST_NAME.to_string()))?;
-                let port = env::var(INCLUSTER_API_PORT_NAME).map_err(err_mapper(INCLUSTER_API_PORT_NAME.to_string()))?;
-                
-                let token_file_contents = &utils::read_file(INCLUSTER_TOKEN_FILE).map_err(ClientInitError::IoError)?;
-                let token = String::from_utf8_lossy(token_file_contents).into_owned();
+                // The HTTPS port is more specific in case the service has other ports as well
+                let port = env::var(INCLUSTER_API_HTTPS_PORT_NAME)
+                    .or_else(|_| env::var(INCLUSTER_API_PORT_NAME))
+                    .map_err(err_mapper(INCLUSTER_API_PORT_NAME.to_string()))?;
+
+                // Bound service account tokens are rotated, so keep reading the file
+                let token = TokenFile::new(INCLUSTER_TOKEN_FILE).map_err(ClientInitError::IoError)?;
 
                 let ca_file = utils::read_file(INCLUSTER_CA_FILE)
                                     .map_err(ClientInitError::IoError)?;
                 let ca = Certificate::from_pem(&ca_file)
                                      .map_err(ClientInitError::InvalidCert)?;
 
-                KubeClient::new(ClientConfig::External { 
-                    auth_info: AuthConfig::Token(token),
-                    api_url: join_host_port(&host, &port),
-                    ca: Some(ca),
-                })
-            },
-            ClientConfig::External { api_url, auth_info, ca } => {
-                if let Some(ca) = ca {
-                    debug!("Adding CA cert");
-                    builder = builder.add_root_certificate(ca);
+                if default_namespace.is_none() {
+                    default_namespace = utils::read_file(INCLUSTER_NAMESPACE_FILE).ok()
+                        .map(|namespace| String::from_utf8_lossy(&namespace).trim().to_string());
                 }
-                let client = builder.build().map_err(ClientInitError::ClientBuildingError)?;
-                Ok(KubeClient {
-                    api_url, auth_info, client
-                })
+
+                (join_host_port(&host, &port), AuthConfig::TokenFile(token), Some(ca))
+            },
+            ClientConfig::External { api_url, auth_info, ca } => (api_url, auth_info, ca),
+        };
+        if let AuthConfig::ClientCertificate | AuthConfig::BasicAuth = auth_info {
+            return Err(ClientInitError::UnsupportedAuth(auth_info));
+        }
+
+        let mut builder = Client::builder()
+            .default_headers(self.default_headers)
+            .timeout(self.read_timeout);
+        if let Some(ca) = ca {
+            debug!("Adding CA cert");
+            builder = builder.add_root_certificate(ca);
+        }
+        for certificate in self.root_certificates {
+            builder = builder.add_root_certificate(certificate);
+        }
+        if let Some(connect_timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(connect_timeout);
+        }
+        if let Some(proxy) = self.proxy {
+            builder = builder.proxy(proxy);
+        }
+        if self.insecure_skip_tls_verify {
+            warn!("Not verifying the certificate of the API server");
+            builder = builder.danger_accept_invalid_certs(true);
+        }
+        if let Some(tls_server_name) = self.tls_server_name {
+            if !is_url_host(&api_url, &tls_server_name) {
+                return Err(ClientInitError::UnsupportedTlsServerName(tls_server_name));
             }
         }
+        let client = builder.build().map_err(ClientInitError::ClientBuildingError)?;
+        Ok(KubeClient {
+            api_url, auth_info, client,
+            retry_policy: RetryPolicy::default(),
+            rate_limiter: Some(RateLimiter::default()),
+            impersonation: self.impersonation,
+            default_namespace: default_namespace.unwrap_or_else(|| DEFAULT_NAMESPACE.to_string()),
+        })
+    }
+}
+
+impl KubeClient {
+    pub fn new(config: ClientConfig) -> Result<KubeClient, ClientInitError> {
+        KubeClientBuilder::new(config).build()
+    }
+
+    /// The namespace of the pod when running in a cluster, or of the kubeconfig context
+    pub fn default_namespace(&self) -> &str {
+        &self.default_namespace
+    }
+
+    /// A client making requests on behalf of the given user and groups, sharing the connection
+    /// pool and rate limit with this one
+    pub fn impersonate<S: Into<String>>(&self, user: S, groups: Vec<String>) -> KubeClient {
+        self.impersonate_as(Impersonation::new(user, groups))
+    }
+
+    /// Like `impersonate`, with control over the uid and extra fields as well
+    pub fn impersonate_as(&self, impersonation: Impersonation) -> KubeClient {
+        KubeClient {
+            impersonation: Some(impersonation),
+            ..self.clone()
+        }
+    }
+
+    /// Use the given policy for retrying failed requests instead of `RetryPolicy::default()`
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> KubeClient {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Limit requests to `qps` per second with bursts of up to `burst` requests, instead of
+    /// client-go's defaults of 5 and 10. The limit is shared with clones made after this call.
+    pub fn with_rate_limit(mut self, qps: f64, burst: u32) -> KubeClient {
+        self.rate_limiter = Some(RateLimiter::new(qps, burst));
+        self
+    }
+
+    /// Use the given rate limiter, e.g. to share it between clients of different clusters
+    pub fn with_rate_limiter(mut self, rate_limiter: RateLimiter) -> KubeClient {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Send requests without any client-side rate limiting
+    pub fn without_rate_limit(mut self) -> KubeClient {
+        self.rate_limiter = None;
+        self
     }
 
     // TODO: more deduplication?
@@ -149,103 +469,437 @@ impl KubeClient {
     // the general case is the same output as input it's easier to restrict this for now.
 
     // Cluster methods
-    pub fn create_cluster_resource<T: KubeKind>(&self, resource: &T) -> RequestResult<T>
+    pub fn create_cluster_resource<T: KubeKind<Scope = Cluster>>(&self, resource: &T) -> RequestResult<T>
     {
         self.post_object(&produce_path::<T>(None, None), resource)
     }
 
-    pub fn replace_cluster_resource<T: KubeKind>(&self, name: &str, resource: &T) -> RequestResult<T> {
+    pub fn replace_cluster_resource<T: KubeKind<Scope = Cluster>>(&self, name: &str, resource: &T) -> RequestResult<T> {
         self.put_object(&produce_path::<T>(None, Some(name)), resource)
     }
 
-    pub fn patch_cluster_resource<T: KubeKind>(&self, name: &str, resource: &T) -> RequestResult<T> {
+    pub fn patch_cluster_resource<T: KubeKind<Scope = Cluster>>(&self, name: &str, resource: &T) -> RequestResult<T> {
         self.patch_object(&produce_path::<T>(None, Some(name)), resource)
     }
 
-    pub fn list_cluster_resource<T: KubeKind>(&self) -> RequestResult<T::List> {
+    pub fn list_cluster_resource<T: KubeKind<Scope = Cluster>>(&self) -> RequestResult<T::List> {
         self.get_object(&produce_path::<T>(None, None))
     }
 
-    pub fn get_cluster_resource<T: KubeKind>(&self, name: &str) -> RequestResult<T> {
+    pub fn list_cluster_resource_with<T: KubeKind<Scope = Cluster>>(&self, options: &ListOptions) -> RequestResult<T::List> {
+        self.list_object(&produce_path::<T>(None, None), options)
+    }
+
+    pub fn get_cluster_resource<T: KubeKind<Scope = Cluster>>(&self, name: &str) -> RequestResult<T> {
         self.get_object(&produce_path::<T>(None, Some(name)))
     }
 
-    pub fn delete_cluster_resource<T: KubeKind>(&self, name: &str) -> RequestResult<T> {
+    pub fn delete_cluster_resource<T: KubeKind<Scope = Cluster>>(&self, name: &str) -> RequestResult<T> {
         self.delete_object(&produce_path::<T>(None, Some(name)))
     }
 
+    pub fn watch_cluster_resource<T: KubeKind<Scope = Cluster>>(&self, resource_version: Option<&str>) -> RequestResult<WatchEvents<T>> {
+        self.watch_object(&watch_path(&produce_path::<T>(None, None), resource_version))
+    }
+
     // Namepsaced methods
-    pub fn create_namespaced_resource<T: KubeKind>(&self, namespace: &str, resource: &T) -> RequestResult<T> {
+    pub fn create_namespaced_resource<T: KubeKind<Scope = Namespaced>>(&self, namespace: &str, resource: &T) -> RequestResult<T> {
         self.post_object(&produce_path::<T>(Some(namespace), None), resource)
     }
 
-    pub fn replace_namespaced_resource<T: KubeKind>(&self, namespace: &str, name: &str, resource: &T) -> RequestResult<T> {
+    pub fn replace_namespaced_resource<T: KubeKind<Scope = Namespaced>>(&self, namespace: &str, name: &str, resource: &T) -> RequestResult<T> {
         self.put_object(&produce_path::<T>(Some(namespace), Some(name)), resource)
     }
 
-    pub fn patch_namespaced_resource<T: KubeKind>(&self, namespace: &str, name: &str, resource: &T) -> RequestResult<T> {
+    pub fn patch_namespaced_resource<T: KubeKind<Scope = Namespaced>>(&self, namespace: &str, name: &str, resource: &T) -> RequestResult<T> {
         self.patch_object(&produce_path::<T>(Some(namespace), Some(name)), resource)
     }
 
-    pub fn get_namespaced_resource<T: KubeKind>(&self, namespace: &str, name: &str) -> RequestResult<T> {
+    pub fn get_namespaced_resource<T: KubeKind<Scope = Namespaced>>(&self, namespace: &str, name: &str) -> RequestResult<T> {
         self.get_object(&produce_path::<T>(Some(namespace), Some(name)))
     }
 
-    pub fn list_namespaced_resource<T: KubeKind>(&self, namespace: &str) -> RequestResult<T::List> {
+    pub fn list_namespaced_resource<T: KubeKind<Scope = Namespaced>>(&self, namespace: &str) -> RequestResult<T::List> {
         self.get_object(&produce_path::<T>(Some(namespace), None))
     }
 
-    pub fn delete_namespaced_resource<T: KubeKind>(&self, namespace: &str, name: &str) -> RequestResult<T> {
+    pub fn list_namespaced_resource_with<T: KubeKind<Scope = Namespaced>>(&self, namespace: &str, options: &ListOptions) -> RequestResult<T::List> {
+        self.list_object(&produce_path::<T>(Some(namespace), None), options)
+    }
+
+    pub fn delete_namespaced_resource<T: KubeKind<Scope = Namespaced>>(&self, namespace: &str, name: &str) -> RequestResult<T> {
         self.delete_object(&produce_path::<T>(Some(namespace), Some(name)))
     }
 
+    pub fn watch_namespaced_resource<T: KubeKind<Scope = Namespaced>>(&self, namespace: &str, resource_version: Option<&str>) -> RequestResult<WatchEvents<T>> {
+        self.watch_object(&watch_path(&produce_path::<T>(Some(namespace), None), resource_version))
+    }
+
+    /// List a namespaced resource in all namespaces
+    pub fn list_all_namespaces_resource<T: KubeKind<Scope = Namespaced>>(&self) -> RequestResult<T::List> {
+        self.get_object(&produce_path::<T>(None, None))
+    }
+
+    pub fn list_all_namespaces_resource_with<T: KubeKind<Scope = Namespaced>>(&self, options: &ListOptions) -> RequestResult<T::List> {
+        self.list_object(&produce_path::<T>(None, None), options)
+    }
+
+    /// Watch a namespaced resource in all namespaces
+    pub fn watch_all_namespaces_resource<T: KubeKind<Scope = Namespaced>>(&self, resource_version: Option<&str>) -> RequestResult<WatchEvents<T>> {
+        self.watch_object(&watch_path(&produce_path::<T>(None, None), resource_version))
+    }
+
+    // Dynamic methods
+    // The namespace is ignored for cluster-scoped resources. Listing and watching a namespaced
+    // resource without a namespace lists or watches it in all namespaces.
+    pub fn create_dynamic(&self, resource: &ApiResource, namespace: Option<&str>, object: &DynamicObject) -> RequestResult<DynamicObject> {
+        self.post_object(&produce_dynamic_path(resource, namespace, None), object)
+    }
+
+    pub fn replace_dynamic(&self, resource: &ApiResource, namespace: Option<&str>, name: &str, object: &DynamicObject) -> RequestResult<DynamicObject> {
+        self.put_object(&produce_dynamic_path(resource, namespace, Some(name)), object)
+    }
+
+    /// Patch an object. Note that strategic merge patches are not supported for custom resources.
+    pub fn patch_dynamic(&self, resource: &ApiResource, namespace: Option<&str>, name: &str,
+                         strategy: PatchStrategy, patch: &Value) -> RequestResult<DynamicObject> {
+        self.patch_object_with(&produce_dynamic_path(resource, namespace, Some(name)), strategy, patch)
+    }
+
+    pub fn get_dynamic(&self, resource: &ApiResource, namespace: Option<&str>, name: &str) -> RequestResult<DynamicObject> {
+        self.get_object(&produce_dynamic_path(resource, namespace, Some(name)))
+    }
+
+    pub fn list_dynamic(&self, resource: &ApiResource, namespace: Option<&str>) -> RequestResult<DynamicObjectList> {
+        self.get_object(&produce_dynamic_path(resource, namespace, None))
+    }
+
+    pub fn list_dynamic_with(&self, resource: &ApiResource, namespace: Option<&str>, options: &ListOptions) -> RequestResult<DynamicObjectList> {
+        self.list_object(&produce_dynamic_path(resource, namespace, None), options)
+    }
+
+    pub fn delete_dynamic(&self, resource: &ApiResource, namespace: Option<&str>, name: &str) -> RequestResult<DynamicObject> {
+        self.delete_object(&produce_dynamic_path(resource, namespace, Some(name)))
+    }
+
+    pub fn watch_dynamic(&self, resource: &ApiResource, namespace: Option<&str>,
+                         resource_version: Option<&str>) -> RequestResult<WatchEvents<DynamicObject>> {
+        self.watch_object(&watch_path(&produce_dynamic_path(resource, namespace, None), resource_version))
+    }
+
+    // Custom resource definitions
+    /// Create a CRD, or replace it if it already exists, and wait until it is established.
+    /// Returns `RequestError::Timeout` if the CRD isn't established within `timeout`.
+    pub fn install_custom_resource_definition(&self, mut crd: CustomResourceDefinition,
+                                              timeout: Duration) -> RequestResult<CustomResourceDefinition> {
+        let name = crd.metadata.name.clone();
+        match self.create_cluster_resource(&crd) {
+            Err(ref error) if error.is_conflict() => {
+                let existing: CustomResourceDefinition = self.get_cluster_resource(&name)?;
+                crd.metadata.resource_version = existing.metadata.resource_version;
+                self.replace_cluster_resource(&name, &crd)?;
+            },
+            result => { result?; },
+        }
+
+        let deadline = Instant::now() + timeout;
+        loop {
+            let crd: CustomResourceDefinition = self.get_cluster_resource(&name)?;
+            if crd.is_established() {
+                return Ok(crd);
+            }
+            if Instant::now() >= deadline {
+                return Err(RequestError::Timeout);
+            }
+            thread::sleep(ESTABLISHED_POLL_INTERVAL);
+        }
+    }
+
+    // Discovery
+    /// Walk `/api`, `/apis` and every group version to discover all resources of the cluster.
+    /// Failing to discover the resources of a group version doesn't fail the discovery, the
+    /// group version is listed in `Discovery::failed` instead.
+    pub fn discover(&self) -> RequestResult<Discovery> {
+        let core: APIVersions = self.get_object("/api")?;
+        let core_group = APIGroup {
+            name: String::new(),
+            preferred_version: core.versions.first().map(|version| GroupVersionForDiscovery {
+                group_version: version.clone(),
+                version: version.clone(),
+            }),
+            versions: core.versions.iter().map(|version| GroupVersionForDiscovery {
+                group_version: version.clone(),
+                version: version.clone(),
+            }).collect(),
+            ..Default::default()
+        };
+        let group_list: APIGroupList = self.get_object("/apis")?;
+
+        let mut groups = vec![core_group];
+        groups.extend(group_list.groups);
+
+        let mut resources = Vec::new();
+        let mut failed = Vec::new();
+        for group in &groups {
+            for version in &group.versions {
+                let path = if group.name.is_empty() {
+                    format!("/api/{}", version.version)
+                } else {
+                    format!("/apis/{}", version.group_version)
+                };
+                match self.get_object(&path) {
+                    Ok(list) => resources.push(list),
+                    Err(error) => {
+                        warn!("Unable to discover the resources of {}: {}", version.group_version, error);
+                        failed.push(GroupDiscoveryFailure {
+                            group_version: version.group_version.clone(),
+                            error,
+                        });
+                    },
+                }
+            }
+        }
+        Ok(Discovery { groups, resources, failed })
+    }
+
+    /// Like `discover`, but reuse the result cached at `path` if it is younger than `max_age`.
+    /// Only complete results are cached.
+    /// Failing to read or write the cache is not an error, the cluster is queried instead.
+    pub fn discover_cached<P: AsRef<Path>>(&self, path: P, max_age: Duration) -> RequestResult<Discovery> {
+        let path = path.as_ref();
+        let age = fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| SystemTime::now().duration_since(modified).ok());
+        if matches!(age, Some(age) if age < max_age) {
+            match Discovery::load(path) {
+                Ok(discovery) => return Ok(discovery),
+                Err(error) => debug!("Unable to read discovery cache {}: {}", path.display(), error),
+            }
+        }
+        let discovery = self.discover()?;
+        // Don't cache partial results, the failed group versions may be back by the next call
+        if !discovery.is_complete() {
+            return Ok(discovery);
+        }
+        if let Err(error) = discovery.save(path) {
+            warn!("Unable to write discovery cache {}: {}", path.display(), error);
+        }
+        Ok(discovery)
+    }
+
+    // Authentication
+    /// Authenticate a token with the API server. `audiences` defaults to the API server's own.
+    pub fn review_token(&self, token: &str, audiences: &[&str]) -> RequestResult<TokenReviewStatus> {
+        self.create_cluster_resource(&TokenReview::new(token, audiences))
+            .map(|review: TokenReview| review.status)
+    }
+
+    /// Mint a token for a service account through its `token` subresource
+    pub fn create_token(&self, namespace: &str, service_account: &str, request: &TokenRequest) -> RequestResult<TokenRequest> {
+        let path = format!("{}/token", produce_path::<ServiceAccount>(Some(namespace), Some(service_account)));
+        self.post_object(&path, request)
+    }
+
+    // Authorization
+    /// Whether the current user may perform `verb` on `resource`, like `kubectl auth can-i`.
+    /// See `ResourceAttributes::new` for the format of `resource`. The `reason` of the status
+    /// may explain the decision.
+    pub fn can_i(&self, verb: &str, resource: &str, namespace: Option<&str>) -> RequestResult<SubjectAccessReviewStatus> {
+        let review = SelfSubjectAccessReview::new(SelfSubjectAccessReviewSpec {
+            resource_attributes: Some(ResourceAttributes::new(verb, resource, namespace)),
+            non_resource_attributes: None,
+        });
+        self.create_cluster_resource(&review).map(|review: SelfSubjectAccessReview| review.status)
+    }
+
+    /// Whether a user may perform an action, e.g. to authorize requests authenticated with
+    /// `review_token`
+    pub fn review_access(&self, spec: SubjectAccessReviewSpec) -> RequestResult<SubjectAccessReviewStatus> {
+        self.create_cluster_resource(&SubjectAccessReview::new(spec))
+            .map(|review: SubjectAccessReview| review.status)
+    }
+
+    /// The actions the current user may perform in a namespace
+    pub fn rules_review(&self, namespace: &str) -> RequestResult<SubjectRulesReviewStatus> {
+        self.create_cluster_resource(&SelfSubjectRulesReview::new(namespace))
+            .map(|review: SelfSubjectRulesReview| review.status)
+    }
+
+    // Retrying updates
+    /// Get a namespaced object, modify it with `update` and replace it. If the object was
+    /// modified in the meantime the server responds with 409 Conflict, and this is retried with
+    /// a freshly read object according to the default `Backoff`.
+    pub fn update_namespaced_resource_with_retry<T, F>(&self, namespace: &str, name: &str, mut update: F) -> RequestResult<T>
+        where T: KubeKind<Scope = Namespaced>,
+              F: FnMut(&mut T),
+    {
+        Backoff::default().retry(|| {
+            let mut resource = self.get_namespaced_resource::<T>(namespace, name)?;
+            update(&mut resource);
+            self.replace_namespaced_resource(namespace, name, &resource)
+        }, |error| error.is_conflict())
+    }
+
+    /// Like `update_namespaced_resource_with_retry`, for cluster-scoped objects
+    pub fn update_cluster_resource_with_retry<T, F>(&self, name: &str, mut update: F) -> RequestResult<T>
+        where T: KubeKind<Scope = Cluster>,
+              F: FnMut(&mut T),
+    {
+        Backoff::default().retry(|| {
+            let mut resource = self.get_cluster_resource::<T>(name)?;
+            update(&mut resource);
+            self.replace_cluster_resource(name, &resource)
+        }, |error| error.is_conflict())
+    }
+
     // Low level methods
     pub fn get_object<T: DeserializeOwned>(&self, path: &str) -> RequestResult<T> {
-        deserialize_api_response(self.request_path::<()>(Method::GET, path, None))
+        deserialize_api_response(self.request_path::<()>(Method::GET, path, None, None))
+    }
+
+    /// GET a collection, passing the options as query parameters
+    pub fn list_object<T: DeserializeOwned>(&self, path: &str, options: &ListOptions) -> RequestResult<T> {
+        let request = self.request(Method::GET, path).query(options);
+        deserialize_api_response(self.send(&Method::GET, request))
     }
 
     pub fn post_object<T: Serialize, U: DeserializeOwned>(&self, path: &str, object: &T) -> RequestResult<U> {
-        deserialize_api_response(self.request_path(Method::POST, path, Some(object)))
+        deserialize_api_response(self.request_path(Method::POST, path, Some(object), None))
     }
 
     pub fn put_object<T: Serialize, U: DeserializeOwned>(&self, path: &str, object: &T) -> RequestResult<U> {
-        deserialize_api_response(self.request_path(Method::PUT, path, Some(object)))
+        deserialize_api_response(self.request_path(Method::PUT, path, Some(object), None))
     }
 
     pub fn patch_object<T: Serialize, U: DeserializeOwned>(&self, path: &str, object: &T) -> RequestResult<U> {
-        deserialize_api_response(self.request_path(Method::PATCH, path, Some(object)))
+        self.patch_object_with(path, PatchStrategy::StrategicMerge, object)
+    }
+
+    pub fn patch_object_with<T: Serialize, U: DeserializeOwned>(&self, path: &str, strategy: PatchStrategy, object: &T) -> RequestResult<U> {
+        deserialize_api_response(self.request_path(Method::PATCH, path, Some(object), Some(strategy.content_type())))
     }
 
     pub fn delete_object<T: DeserializeOwned>(&self, path: &str) -> RequestResult<T> {
-        deserialize_api_response(self.request_path::<()>(Method::DELETE, path, None))
+        deserialize_api_response(self.request_path::<()>(Method::DELETE, path, None, None))
+    }
+
+    /// Start a watch, `path` is expected to contain the `watch` query parameter
+    pub fn watch_object<T: DeserializeOwned>(&self, path: &str) -> RequestResult<WatchEvents<T>> {
+        let response = self.request_path::<()>(Method::GET, path, None, None)
+                           .map_err(RequestError::TransportError)?;
+        if !response.status().is_success() {
+            return Err(RequestError::HttpError(response));
+        }
+        Ok(WatchEvents {
+            events: Deserializer::from_reader(response).into_iter(),
+        })
     }
 
     fn authorize_request(&self, request: RequestBuilder) -> RequestBuilder {
         // TODO: add logic for different auth methods
         match self.auth_info {
             AuthConfig::Token(ref bearer) =>
-                request.header(AUTHORIZATION, format!("Bearer {}", bearer.clone())),
-            _ => unimplemented!(),
+                request.header(AUTHORIZATION, format!("Bearer {}", bearer)),
+            AuthConfig::TokenFile(ref file) =>
+                request.header(AUTHORIZATION, format!("Bearer {}", file.token())),
+            // Rejected when building the client
+            AuthConfig::ClientCertificate | AuthConfig::BasicAuth => request,
         }
     }
 
-    fn request_path<T: Serialize>(&self, method: Method, path: &str, body: Option<&T>) -> HttpResult<Response> {
+    // A request without credentials, those are added by `send` for every attempt
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
         let uri = format!("{}{}", self.api_url, path);
-        let is_patching = method == Method::PATCH;
-        let mut request = self.authorize_request(self.client.request(method, &uri));
+        let mut request = self.client.request(method, &uri);
+        if let Some(ref impersonation) = self.impersonation {
+            for (name, value) in impersonation.headers() {
+                request = request.header(name.as_str(), value);
+            }
+        }
+        request
+    }
+
+    fn request_path<T: Serialize>(&self, method: Method, path: &str, body: Option<&T>, content_type: Option<&'static str>) -> HttpResult<Response> {
+        let mut request = self.request(method.clone(), path);
         if let Some(body) = body {
             request = request.json(body);
         }
-        // TODO: this is very heavy handed, there's probably a more flexible way to do this
-        if is_patching {
-            request = request.header(CONTENT_TYPE, "application/strategic-merge-patch+json");
+        // Overrides the JSON content type set above, `header` would add a second one
+        if let Some(content_type) = content_type {
+            let mut headers = HeaderMap::new();
+            headers.insert(CONTENT_TYPE, HeaderValue::from_static(content_type));
+            request = request.headers(headers);
+        }
+        self.send(&method, request)
+    }
+
+    // Send a request, retrying it according to the retry policy. Every attempt is rate limited.
+    // If a token read from a file is rejected the file is read again and the request resent once.
+    fn send(&self, method: &Method, request: RequestBuilder) -> HttpResult<Response> {
+        let policy = &self.retry_policy;
+        let mut retry = 0;
+        let mut reloaded_token = false;
+        loop {
+            if let Some(ref rate_limiter) = self.rate_limiter {
+                rate_limiter.acquire();
+            }
+            let can_retry = retry + 1 < policy.backoff.steps && policy.applies_to(method);
+            let token_file = match self.auth_info {
+                AuthConfig::TokenFile(ref file) if !reloaded_token => Some(file),
+                _ => None,
+            };
+            // A copy is needed for sending the request again, which isn't possible with streamed bodies
+            let attempt = if can_retry || token_file.is_some() {
+                request.try_clone()
+            } else {
+                None
+            };
+            let attempt = match attempt {
+                Some(attempt) => attempt,
+                None => return self.authorize_request(request).send(),
+            };
+            let delay = match (self.authorize_request(attempt).send(), token_file) {
+                (Ok(response), Some(file)) if response.status() == StatusCode::UNAUTHORIZED => {
+                    if let Err(error) = file.reload() {
+                        warn!("Failed to reload token from {}: {}", file.path().display(), error);
+                        return Ok(response);
+                    }
+                    debug!("Request was unauthorized, retrying with a reloaded token");
+                    reloaded_token = true;
+                    continue;
+                },
+                (Err(ref error), _) if can_retry && !error.is_serialization() && !error.is_redirect() => {
+                    debug!("Request failed, retrying: {}", error);
+                    policy.delay(retry)
+                },
+                (Ok(response), _) if can_retry && is_transient_status(response.status()) => {
+                    debug!("Request failed with {}, retrying", response.status());
+                    // The server's delay is capped like ours, so a bogus value can't block for hours
+                    retry_after(response)
+                        .map(|delay| delay.min(policy.backoff.cap))
+                        .unwrap_or_else(|| policy.delay(retry))
+                },
+                (result, _) => return result,
+            };
+            thread::sleep(delay);
+            retry += 1;
         }
-        request.send()
     }
 }
 
 // TODO: how should we deal with subresources?
-fn produce_path<T: KubeKind>(namespace: Option<&str>, resource: Option<&str>) -> String {
+pub(crate) fn produce_path<T: KubeKind>(namespace: Option<&str>, resource: Option<&str>) -> String {
+    produce_resource_path(T::API_GROUP, T::API_VERSION, T::KIND_NAME, namespace, resource)
+}
+
+fn produce_dynamic_path(resource: &ApiResource, namespace: Option<&str>, name: Option<&str>) -> String {
+    let namespace = if resource.namespaced { namespace } else { None };
+    let group = if resource.is_core() { "core" } else { &resource.group };
+    produce_resource_path(group, &resource.version, &resource.plural, namespace, name)
+}
+
+fn produce_resource_path(group: &str, version: &str, kind: &str, namespace: Option<&str>, resource: Option<&str>) -> String {
     // First parameter is API path, which consists of:
     // /api/<version> for core
     // /apis/<api group>/<version> for everything else
@@ -253,10 +907,10 @@ fn produce_path<T: KubeKind>(namespace: Option<&str>, resource: Option<&str>) ->
     // Third is kind name
     // Finally, an optional object name
     format!("/{api}/{namespace}{kind}/{object}",
-            api = if T::API_GROUP == "core" {
-                format!("api/{}", T::API_VERSION)
+            api = if group == "core" {
+                format!("api/{}", version)
             } else {
-                format!("apis/{}/{}", T::API_GROUP, T::API_VERSION)
+                format!("apis/{}/{}", group, version)
             },
             namespace = if let Some(namespace) = namespace {
                 format!("namespaces/{}/", namespace)
@@ -264,7 +918,43 @@ fn produce_path<T: KubeKind>(namespace: Option<&str>, resource: Option<&str>) ->
                 "".to_string()
             },
             object = resource.unwrap_or(""),
-            kind = T::KIND_NAME)
+            kind = kind)
+}
+
+fn watch_path(path: &str, resource_version: Option<&str>) -> String {
+    match resource_version {
+        Some(resource_version) => format!("{}?watch=1&resourceVersion={}", path, resource_version),
+        None => format!("{}?watch=1", path),
+    }
+}
+
+// Throttling and unavailable API servers, e.g. during restarts
+fn is_transient_status(status: StatusCode) -> bool {
+    matches!(status, StatusCode::TOO_MANY_REQUESTS |
+                     StatusCode::BAD_GATEWAY |
+                     StatusCode::SERVICE_UNAVAILABLE |
+                     StatusCode::GATEWAY_TIMEOUT)
+}
+
+// Whether `name` is the host of `url`, i.e. the name its certificate is verified for anyway
+fn is_url_host(url: &str, name: &str) -> bool {
+    Url::parse(url).ok().and_then(|url| url.host_str().map(|host| host.eq_ignore_ascii_case(name))).unwrap_or(false)
+}
+
+// The delay requested by the API server, either in the Retry-After header or the returned Status
+fn retry_after(response: Response) -> Option<Duration> {
+    let header = response.headers().get(RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse().ok());
+    if let Some(seconds) = header {
+        return Some(Duration::from_secs(seconds));
+    }
+    let status: Status = serde_json::from_reader(response).ok()?;
+    match status.details {
+        Some(ref details) if details.retry_after_seconds > 0 =>
+            Some(Duration::from_secs(details.retry_after_seconds as u64)),
+        _ => None,
+    }
 }
 
 fn deserialize_api_response<T: DeserializeOwned>(response: HttpResult<Response>) -> RequestResult<T> {
@@ -277,3 +967,26 @@ fn deserialize_api_response<T: DeserializeOwned>(response: HttpResult<Response>)
                 }
             })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn builder(api_url: &str) -> KubeClientBuilder {
+        KubeClientBuilder::new(ClientConfig::External {
+            api_url: api_url.to_string(),
+            auth_info: AuthConfig::Token("token".to_string()),
+            ca: None,
+        })
+    }
+
+    #[test]
+    fn tls_server_name_must_be_the_url_host() {
+        assert!(builder("https://10.0.0.1:6443").build().is_ok());
+        assert!(builder("https://api.example.com:6443").tls_server_name("API.example.com").build().is_ok());
+        match builder("https://10.0.0.1:6443").tls_server_name("api.example.com").build() {
+            Err(ClientInitError::UnsupportedTlsServerName(name)) => assert_eq!(name, "api.example.com"),
+            result => panic!("unexpected result {:?}", result.map(|client| client.api_url)),
+        }
+    }
+}
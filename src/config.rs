@@ -1,4 +1,9 @@
+use std::collections::HashMap;
 use std::fmt;
+use std::io::Result as IoResult;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use base64;
 use reqwest::Certificate;
 
@@ -22,27 +27,20 @@ pub enum KubeconfigParseError {
     MissingFile(String),
     InvalidCertificate(String),
     InvalidBase64,
+    InvalidProxyUrl(String),
+    /// The user has no credentials this client supports, e.g. only client certificates or an
+    /// auth provider
+    UnsupportedAuth(String),
+    /// The cluster sets `tls-server-name` to a name other than the host of its server, which the
+    /// HTTP client can't verify the certificate for
+    UnsupportedTlsServerName(String),
 }
 
 impl ClientConfig {
     // XXX: jfc, this sucks
     pub fn from_kubeconfig(config: kubeconfig::Config, 
                            context: Option<String>) -> Result<ClientConfig, KubeconfigParseError> {
-        // Take current_context from the config file, or if the user specified one prefer that
-        let context_name = context.unwrap_or_else(|| config.current_context.clone());
-
-        // Find the desired context
-        let context = &config.contexts.iter()
-                                      .find(|context| context.name == context_name)
-                                      .ok_or_else(|| KubeconfigParseError::MissingContext(context_name))?.context;
-        // Find the cluster referenced in the context
-        let cluster = &config.clusters.iter()
-                                      .find(|cluster| cluster.name == context.cluster)
-                                      .ok_or_else(|| KubeconfigParseError::MissingCluster(context.cluster.clone()))?.cluster;
-        // Find the user referenced in the context
-        let user = &config.users.iter()
-                                .find(|user| user.name == context.user)
-                                .ok_or_else(|| KubeconfigParseError::MissingUser(context.user.clone()))?.user;
+        let (_, cluster, user) = kubeconfig::resolve_context(&config, context)?;
         // If the cluster specified a CA cert retrieve that here
         let ca = if let Some(ref ca_data) = cluster.certificate_authority_data {
             // CA specified inline as base64 PEM
@@ -59,7 +57,7 @@ impl ClientConfig {
         };
         Ok(ClientConfig::External {
             api_url: cluster.server.clone(),
-            auth_info: AuthConfig::from_kubeconfig_user(user),
+            auth_info: AuthConfig::from_kubeconfig_user(user)?,
             ca: ca,
         })
     }
@@ -68,6 +66,8 @@ impl ClientConfig {
 #[derive(Clone)]
 pub enum AuthConfig {
     Token(String),
+    /// A bearer token read from a file, which is read again when it may have been rotated
+    TokenFile(TokenFile),
     ClientCertificate,
     BasicAuth,
 }
@@ -76,32 +76,183 @@ impl fmt::Debug for AuthConfig {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         match *self {
             AuthConfig::Token(_) => write!(f, "Token authentication"),
-            _ => unimplemented!("Other auth methods are not yet implemented"),
+            AuthConfig::TokenFile(ref file) => write!(f, "Token authentication from {}", file.path().display()),
+            AuthConfig::ClientCertificate => write!(f, "Client certificate authentication"),
+            AuthConfig::BasicAuth => write!(f, "Basic authentication"),
         }
     }
 }
 
 impl AuthConfig {
-    pub fn from_kubeconfig_user(user: &kubeconfig::AuthInfo) -> AuthConfig {
+    pub fn from_kubeconfig_user(user: &kubeconfig::AuthInfo) -> Result<AuthConfig, KubeconfigParseError> {
         // TODO: other auth methods from kubeconfig files
         if let Some(ref token) = user.token {
-            return AuthConfig::Token(token.clone());
+            return Ok(AuthConfig::Token(token.clone()));
+        }
+        if let Some(ref path) = user.token_file {
+            let file = TokenFile::new(path).map_err(|_| KubeconfigParseError::MissingFile(path.clone()))?;
+            return Ok(AuthConfig::TokenFile(file));
+        }
+        let method = if user.client_certificate.is_some() || user.client_certificate_data.is_some() {
+            "client certificate"
+        } else if user.username.is_some() {
+            "basic auth"
+        } else {
+            "none"
+        };
+        Err(KubeconfigParseError::UnsupportedAuth(method.to_string()))
+    }
+}
+
+// How long a token read from a file is used before reading the file again. Kubelet rotates
+// projected service account tokens well before they expire, so this doesn't need to be short.
+const TOKEN_FILE_RELOAD_INTERVAL: Duration = Duration::from_secs(60);
+
+/// A bearer token file, e.g. a projected service account token. The token is read again once
+/// a minute or when `reload` is called, clones share the token.
+#[derive(Clone)]
+pub struct TokenFile {
+    path: PathBuf,
+    token: Arc<Mutex<(String, Instant)>>,
+}
+
+impl TokenFile {
+    pub fn new<P: AsRef<Path>>(path: P) -> IoResult<TokenFile> {
+        let path = path.as_ref().to_path_buf();
+        let token = read_token(&path)?;
+        Ok(TokenFile {
+            path,
+            token: Arc::new(Mutex::new((token, Instant::now()))),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The current token, read again if it's older than a minute. If the file can't be read the
+    /// previous token is used.
+    pub fn token(&self) -> String {
+        let mut token = self.token.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if token.1.elapsed() >= TOKEN_FILE_RELOAD_INTERVAL {
+            match read_token(&self.path) {
+                Ok(new_token) => *token = (new_token, Instant::now()),
+                Err(error) => warn!("Failed to reload token from {}: {}", self.path.display(), error),
+            }
+        }
+        token.0.clone()
+    }
+
+    /// Read the token again, e.g. after the API server rejected the current one
+    pub fn reload(&self) -> IoResult<()> {
+        let new_token = read_token(&self.path)?;
+        *self.token.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = (new_token, Instant::now());
+        Ok(())
+    }
+}
+
+fn read_token(path: &Path) -> IoResult<String> {
+    let contents = utils::read_file(path)?;
+    Ok(String::from_utf8_lossy(&contents).trim().to_string())
+}
+
+/// The user requests are made on behalf of, using the `Impersonate-*` headers. The
+/// authenticated user needs to be allowed to `impersonate` the user, groups, uid and extras.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Impersonation {
+    pub user: String,
+    pub uid: Option<String>,
+    pub groups: Vec<String>,
+    pub extra: HashMap<String, Vec<String>>,
+}
+
+impl Impersonation {
+    pub fn new<S: Into<String>>(user: S, groups: Vec<String>) -> Impersonation {
+        Impersonation {
+            user: user.into(),
+            groups,
+            ..Impersonation::default()
+        }
+    }
+
+    /// The impersonation configured by `as`, `as-uid`, `as-groups` and `as-user-extra`, if any
+    pub fn from_kubeconfig_user(user: &kubeconfig::AuthInfo) -> Option<Impersonation> {
+        let name = user.impersonate.as_ref()?;
+        Some(Impersonation {
+            user: name.clone(),
+            uid: user.impersonate_uid.clone(),
+            groups: user.impersonate_groups.clone().unwrap_or_default(),
+            extra: user.impersonate_user_extra.clone().unwrap_or_default(),
+        })
+    }
+
+    /// The headers to send, extra keys are percent-encoded as the API server expects
+    pub fn headers(&self) -> Vec<(String, String)> {
+        let mut headers = vec![("Impersonate-User".to_string(), self.user.clone())];
+        if let Some(ref uid) = self.uid {
+            headers.push(("Impersonate-Uid".to_string(), uid.clone()));
+        }
+        for group in &self.groups {
+            headers.push(("Impersonate-Group".to_string(), group.clone()));
+        }
+        for (key, values) in &self.extra {
+            let name = format!("Impersonate-Extra-{}", percent_encode_header_name(key));
+            for value in values {
+                headers.push((name.clone(), value.clone()));
+            }
+        }
+        headers
+    }
+}
+
+// Escape the characters that aren't allowed in header names
+fn percent_encode_header_name(name: &str) -> String {
+    let mut encoded = String::with_capacity(name.len());
+    for byte in name.bytes() {
+        if byte.is_ascii_alphanumeric() || b"!#$&'*+-.^_`|~".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
         }
-        unimplemented!("Reading other auth methods from kubeconfigs is not implemented yet");
     }
+    encoded
 }
 
 pub mod kubeconfig {
+    use std::collections::HashMap;
     use std::path::Path;
     use std::fs::File;
     use serde_yaml;
 
+    use super::KubeconfigParseError;
+
     pub fn read_config<T: AsRef<Path>>(path: T) -> Config {
         // TODO: error handling
         let file = File::open(path).expect("unable to open file");
         serde_yaml::from_reader(file).unwrap()
     }
 
+    /// Find the context with the given name, or the current context, and the cluster and user
+    /// it references
+    pub fn resolve_context(config: &Config, context: Option<String>) -> Result<(&Context, &Cluster, &AuthInfo), KubeconfigParseError> {
+        // Take current_context from the config file, or if the user specified one prefer that
+        let context_name = context.unwrap_or_else(|| config.current_context.clone());
+
+        // Find the desired context
+        let context = &config.contexts.iter()
+                                      .find(|context| context.name == context_name)
+                                      .ok_or_else(|| KubeconfigParseError::MissingContext(context_name))?.context;
+        // Find the cluster referenced in the context
+        let cluster = &config.clusters.iter()
+                                      .find(|cluster| cluster.name == context.cluster)
+                                      .ok_or_else(|| KubeconfigParseError::MissingCluster(context.cluster.clone()))?.cluster;
+        // Find the user referenced in the context
+        let user = &config.users.iter()
+                                .find(|user| user.name == context.user)
+                                .ok_or_else(|| KubeconfigParseError::MissingUser(context.user.clone()))?.user;
+        Ok((context, cluster, user))
+    }
+
     #[derive(Serialize, Deserialize)]
     pub struct Config {
         pub kind: String,
@@ -129,6 +280,10 @@ pub mod kubeconfig {
         pub certificate_authority: Option<String>,
         #[serde(rename = "certificate-authority-data")]
         pub certificate_authority_data: Option<String>,
+        #[serde(rename = "tls-server-name")]
+        pub tls_server_name: Option<String>,
+        #[serde(rename = "proxy-url")]
+        pub proxy_url: Option<String>,
     }
 
     #[derive(Serialize, Deserialize)]
@@ -157,8 +312,12 @@ pub mod kubeconfig {
         // Impersonation
         #[serde(rename = "as")]
         pub impersonate: Option<String>,
+        #[serde(rename = "as-uid")]
+        pub impersonate_uid: Option<String>,
         #[serde(rename = "as-groups")]
         pub impersonate_groups: Option<Vec<String>>,
+        #[serde(rename = "as-user-extra")]
+        pub impersonate_user_extra: Option<HashMap<String, Vec<String>>>,
 
         // Basic auth
         pub username: Option<String>,
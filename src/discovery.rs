@@ -0,0 +1,105 @@
+//! Discovery of the API groups and resources served by a cluster.
+use std::fs::{self, File};
+use std::io::Result as IoResult;
+use std::path::Path;
+
+use serde_json;
+
+use crate::api::dynamic::ApiResource;
+use crate::apimachinery::apis::meta::v1::{
+    APIGroup,
+    APIResource,
+    APIResourceList,
+    GroupVersionKind,
+    GroupVersionResource,
+};
+use crate::client::RequestError;
+
+/// The result of discovering the APIs of a cluster
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct Discovery {
+    /// All API groups, including the core group which has an empty name
+    pub groups: Vec<APIGroup>,
+    /// The resources of every group version
+    pub resources: Vec<APIResourceList>,
+    /// The group versions whose resources could not be discovered, e.g. aggregated APIs whose
+    /// server is unavailable. These aren't saved.
+    #[serde(skip)]
+    pub failed: Vec<GroupDiscoveryFailure>,
+}
+
+/// A group version whose resources could not be discovered
+#[derive(Debug)]
+pub struct GroupDiscoveryFailure {
+    pub group_version: String,
+    pub error: RequestError,
+}
+
+// The group version string used in `apiVersion`, e.g. `apps/v1` or `v1` for the core group
+fn group_version(group: &str, version: &str) -> String {
+    if group.is_empty() || group == "core" {
+        version.to_string()
+    } else {
+        format!("{}/{}", group, version)
+    }
+}
+
+impl Discovery {
+    /// Read a discovery result previously saved with `save`
+    pub fn load<P: AsRef<Path>>(path: P) -> IoResult<Discovery> {
+        Ok(serde_json::from_reader(File::open(path)?)?)
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> IoResult<()> {
+        if let Some(parent) = path.as_ref().parent() {
+            fs::create_dir_all(parent)?;
+        }
+        Ok(serde_json::to_writer(File::create(path)?, self)?)
+    }
+
+    /// Whether the resources of all group versions were discovered
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty()
+    }
+
+    pub fn group(&self, name: &str) -> Option<&APIGroup> {
+        self.groups.iter().find(|group| group.name == name)
+    }
+
+    /// The resources of a group version, `group` is empty for the core group
+    pub fn resources_for(&self, group: &str, version: &str) -> Option<&APIResourceList> {
+        let group_version = group_version(group, version);
+        self.resources.iter().find(|list| list.group_version == group_version)
+    }
+
+    /// The subresources of a resource, e.g. `status` and `scale` for `deployments`
+    pub fn subresources(&self, group: &str, version: &str, resource: &str) -> Vec<&APIResource> {
+        let prefix = format!("{}/", resource);
+        self.resources_for(group, version)
+            .map(|list| list.resources.iter().filter(|r| r.name.starts_with(&prefix)).collect())
+            .unwrap_or_default()
+    }
+
+    /// Find the resource serving a kind
+    pub fn resource_for_kind(&self, kind: &GroupVersionKind) -> Option<&APIResource> {
+        self.resources_for(&kind.group, &kind.version)?
+            .resources.iter()
+            .find(|resource| !resource.is_subresource() && resource.kind == kind.kind)
+    }
+
+    /// Map a kind to the resource it is served as
+    pub fn resolve_kind(&self, kind: &GroupVersionKind) -> Option<GroupVersionResource> {
+        self.resource_for_kind(kind).map(|resource| GroupVersionResource {
+            group: kind.group.clone(),
+            version: kind.version.clone(),
+            resource: resource.name.clone(),
+        })
+    }
+
+    /// Describe a kind for use with the dynamic client methods
+    pub fn api_resource(&self, kind: &GroupVersionKind) -> Option<ApiResource> {
+        self.resource_for_kind(kind).map(|resource| {
+            ApiResource::new(&kind.group, &kind.version, &kind.kind, &resource.name, resource.namespaced)
+        })
+    }
+}
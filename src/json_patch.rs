@@ -0,0 +1,282 @@
+//! RFC 6902 JSON Patch generation and application.
+//!
+//! Patches are generated by serializing both versions of an object with serde_json and comparing
+//! the resulting values. The generated patches only ever contain `add`, `remove` and `replace`
+//! operations, but all operations are supported when applying a patch.
+use std::fmt;
+use std::error::Error as StdError;
+
+use serde::Serialize;
+use serde_json::{self, Value};
+
+#[serde(tag = "op", rename_all = "lowercase")]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum PatchOperation {
+    Add { path: String, value: Value },
+    Remove { path: String },
+    Replace { path: String, value: Value },
+    Move { from: String, path: String },
+    Copy { from: String, path: String },
+    Test { path: String, value: Value },
+}
+
+pub type Patch = Vec<PatchOperation>;
+
+/// Errors that can occur while applying a patch
+#[derive(Debug)]
+pub enum PatchError {
+    /// The JSON Pointer is not valid, e.g. doesn't start with `/` or has a bad array index
+    InvalidPointer(String),
+    /// The location referenced by the JSON Pointer does not exist
+    PathNotFound(String),
+    /// A `test` operation did not match the document
+    TestFailed(String),
+    /// A `move` operation would move a value into one of its own children
+    MoveIntoChild(String),
+}
+
+impl fmt::Display for PatchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PatchError::InvalidPointer(path) => write!(f, "Invalid JSON Pointer: {}", path),
+            PatchError::PathNotFound(path) => write!(f, "Path does not exist: {}", path),
+            PatchError::TestFailed(path) => write!(f, "Test operation failed at {}", path),
+            PatchError::MoveIntoChild(path) => write!(f, "Cannot move a value into its own child: {}", path),
+        }
+    }
+}
+
+impl StdError for PatchError {}
+
+/// Produce the patch that turns `old` into `new`
+pub fn diff<T: Serialize>(old: &T, new: &T) -> Result<Patch, serde_json::Error> {
+    Ok(diff_values(&serde_json::to_value(old)?, &serde_json::to_value(new)?))
+}
+
+/// Produce the patch that turns the JSON value `old` into `new`
+pub fn diff_values(old: &Value, new: &Value) -> Patch {
+    let mut patch = Vec::new();
+    diff_into(&mut patch, String::new(), old, new);
+    patch
+}
+
+fn diff_into(patch: &mut Patch, path: String, old: &Value, new: &Value) {
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => {
+            for (key, old_value) in old {
+                let path = format!("{}/{}", path, escape(key));
+                match new.get(key) {
+                    Some(new_value) => diff_into(patch, path, old_value, new_value),
+                    None => patch.push(PatchOperation::Remove { path }),
+                }
+            }
+            for (key, new_value) in new {
+                if !old.contains_key(key) {
+                    patch.push(PatchOperation::Add {
+                        path: format!("{}/{}", path, escape(key)),
+                        value: new_value.clone(),
+                    });
+                }
+            }
+        },
+        (Value::Array(old), Value::Array(new)) => {
+            for (index, (old_value, new_value)) in old.iter().zip(new).enumerate() {
+                diff_into(patch, format!("{}/{}", path, index), old_value, new_value);
+            }
+            for (index, new_value) in new.iter().enumerate().skip(old.len()) {
+                patch.push(PatchOperation::Add {
+                    path: format!("{}/{}", path, index),
+                    value: new_value.clone(),
+                });
+            }
+            // Remove from the back so the indices of the remaining elements don't shift
+            for index in (new.len()..old.len()).rev() {
+                patch.push(PatchOperation::Remove { path: format!("{}/{}", path, index) });
+            }
+        },
+        (old, new) => if old != new {
+            patch.push(PatchOperation::Replace { path, value: new.clone() });
+        },
+    }
+}
+
+/// Escape a single reference token for use in a JSON Pointer
+pub fn escape(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+/// Reverse of `escape`
+pub fn unescape(token: &str) -> String {
+    token.replace("~1", "/").replace("~0", "~")
+}
+
+/// Apply a patch to a JSON value.
+/// Operations are applied in order. The patch is atomic, if any operation fails the value is left
+/// unchanged.
+pub fn apply(target: &mut Value, patch: &[PatchOperation]) -> Result<(), PatchError> {
+    let mut patched = target.clone();
+    for operation in patch {
+        apply_operation(&mut patched, operation)?;
+    }
+    *target = patched;
+    Ok(())
+}
+
+fn apply_operation(target: &mut Value, operation: &PatchOperation) -> Result<(), PatchError> {
+    match operation {
+        PatchOperation::Add { path, value } => add(target, path, value.clone())?,
+        PatchOperation::Remove { path } => { remove(target, path)?; },
+        PatchOperation::Replace { path, value } => {
+            *pointer_mut(target, path)? = value.clone();
+        },
+        PatchOperation::Move { from, path } => {
+            // RFC 6902 4.4: the location must not be a proper prefix of the path
+            if path.starts_with(from.as_str()) && path[from.len()..].starts_with('/') {
+                return Err(PatchError::MoveIntoChild(path.clone()));
+            }
+            let value = remove(target, from)?;
+            add(target, path, value)?;
+        },
+        PatchOperation::Copy { from, path } => {
+            let value = pointer(target, from)?.clone();
+            add(target, path, value)?;
+        },
+        PatchOperation::Test { path, value } => if pointer(target, path)? != value {
+            return Err(PatchError::TestFailed(path.clone()));
+        },
+    }
+    Ok(())
+}
+
+// Split a pointer into the pointer of its parent and its last (unescaped) token
+fn split_pointer(path: &str) -> Result<(&str, String), PatchError> {
+    match path.rfind('/') {
+        Some(index) => Ok((&path[..index], unescape(&path[index + 1..]))),
+        None => Err(PatchError::InvalidPointer(path.to_string())),
+    }
+}
+
+fn pointer<'a>(target: &'a Value, path: &str) -> Result<&'a Value, PatchError> {
+    if !path.is_empty() && !path.starts_with('/') {
+        return Err(PatchError::InvalidPointer(path.to_string()));
+    }
+    target.pointer(path).ok_or_else(|| PatchError::PathNotFound(path.to_string()))
+}
+
+fn pointer_mut<'a>(target: &'a mut Value, path: &str) -> Result<&'a mut Value, PatchError> {
+    if !path.is_empty() && !path.starts_with('/') {
+        return Err(PatchError::InvalidPointer(path.to_string()));
+    }
+    target.pointer_mut(path).ok_or_else(|| PatchError::PathNotFound(path.to_string()))
+}
+
+fn array_index(path: &str, token: &str, len: usize) -> Result<usize, PatchError> {
+    // Leading zeroes are not allowed by RFC 6901
+    if token.len() > 1 && token.starts_with('0') {
+        return Err(PatchError::InvalidPointer(path.to_string()));
+    }
+    let index = token.parse::<usize>().map_err(|_| PatchError::InvalidPointer(path.to_string()))?;
+    if index > len {
+        return Err(PatchError::PathNotFound(path.to_string()));
+    }
+    Ok(index)
+}
+
+fn add(target: &mut Value, path: &str, value: Value) -> Result<(), PatchError> {
+    if path.is_empty() {
+        *target = value;
+        return Ok(());
+    }
+    let (parent, token) = split_pointer(path)?;
+    match pointer_mut(target, parent)? {
+        Value::Object(map) => {
+            map.insert(token, value);
+        },
+        Value::Array(array) => {
+            if token == "-" {
+                array.push(value);
+            } else {
+                let index = array_index(path, &token, array.len())?;
+                array.insert(index, value);
+            }
+        },
+        _ => return Err(PatchError::PathNotFound(path.to_string())),
+    }
+    Ok(())
+}
+
+fn remove(target: &mut Value, path: &str) -> Result<Value, PatchError> {
+    let (parent, token) = split_pointer(path)?;
+    match pointer_mut(target, parent)? {
+        Value::Object(map) => map.remove(&token).ok_or_else(|| PatchError::PathNotFound(path.to_string())),
+        Value::Array(array) => {
+            let index = array_index(path, &token, array.len())?;
+            if index == array.len() {
+                return Err(PatchError::PathNotFound(path.to_string()));
+            }
+            Ok(array.remove(index))
+        },
+        _ => Err(PatchError::PathNotFound(path.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn patch(operations: Value) -> Patch {
+        serde_json::from_value(operations).unwrap()
+    }
+
+    #[test]
+    fn apply_all_operations() {
+        let mut target = json!({ "a": { "b": 1 }, "list": [1, 2] });
+        let operations = patch(json!([
+            { "op": "add", "path": "/list/1", "value": 5 },
+            { "op": "remove", "path": "/list/0" },
+            { "op": "replace", "path": "/a/b", "value": 2 },
+            { "op": "copy", "from": "/a", "path": "/c" },
+            { "op": "move", "from": "/c/b", "path": "/d" },
+            { "op": "test", "path": "/d", "value": 2 },
+        ]));
+        apply(&mut target, &operations).unwrap();
+        assert_eq!(target, json!({ "a": { "b": 2 }, "c": {}, "d": 2, "list": [5, 2] }));
+    }
+
+    #[test]
+    fn failed_patch_leaves_target_unchanged() {
+        let mut target = json!({ "a": 1 });
+        let operations = patch(json!([
+            { "op": "add", "path": "/b", "value": 2 },
+            { "op": "test", "path": "/a", "value": 3 },
+        ]));
+        assert!(apply(&mut target, &operations).is_err());
+        assert_eq!(target, json!({ "a": 1 }));
+    }
+
+    #[test]
+    fn move_into_child_is_rejected() {
+        let mut target = json!({ "a": { "b": 1 } });
+        let operations = patch(json!([{ "op": "move", "from": "/a", "path": "/a/b" }]));
+        match apply(&mut target, &operations) {
+            Err(PatchError::MoveIntoChild(_)) => (),
+            result => panic!("unexpected result {:?}", result),
+        }
+        assert_eq!(target, json!({ "a": { "b": 1 } }));
+
+        // A sibling sharing the prefix is fine
+        let operations = patch(json!([{ "op": "move", "from": "/a", "path": "/ab" }]));
+        apply(&mut target, &operations).unwrap();
+        assert_eq!(target, json!({ "ab": { "b": 1 } }));
+    }
+
+    #[test]
+    fn diff_round_trips() {
+        let old = json!({ "a": 1, "b": [1, 2, 3], "c": { "d": "e" } });
+        let new = json!({ "a": 2, "b": [1, 3], "f": null });
+        let mut patched = old.clone();
+        apply(&mut patched, &diff_values(&old, &new)).unwrap();
+        assert_eq!(patched, new);
+    }
+}
@@ -7,6 +7,10 @@ extern crate serde_yaml;
 // HTTP client library
 extern crate reqwest;
 
+// HTTP server library, used by the admission webhook server
+#[cfg(any(feature = "webhook", feature = "testing"))]
+extern crate tiny_http;
+
 // Logging macros
 #[macro_use] extern crate log;
 
@@ -18,12 +22,30 @@ extern crate base64;
 extern crate uuid;
 extern crate chrono;
 extern crate num_traits;
+// Jitter of retry delays
+extern crate rand;
+
+// #[derive(CustomResource)]
+#[cfg(feature = "derive")]
+extern crate kubeclient_derive;
 
 mod utils;
 pub mod client;
 pub mod api;
 pub mod apimachinery;
 pub mod config;
+pub mod discovery;
+pub mod json_patch;
+pub mod ratelimit;
+pub mod retry;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(feature = "webhook")]
+pub mod webhook;
 
-pub use crate::client::{RequestResult, RequestError, KubeClient};
-pub use crate::config::{ClientConfig, AuthConfig};
+pub use crate::client::{RequestResult, RequestError, KubeClient, KubeClientBuilder, PatchStrategy, WatchEvents};
+pub use crate::config::{ClientConfig, AuthConfig, Impersonation};
+pub use crate::ratelimit::RateLimiter;
+pub use crate::retry::{Backoff, RetryPolicy};
+#[cfg(feature = "derive")]
+pub use kubeclient_derive::{CustomResource, StructuralSchema};
@@ -0,0 +1,159 @@
+//! Client-side rate limiting of requests to the API server
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// The default sustained rate of requests, the same as client-go's
+pub const DEFAULT_QPS: f64 = 5.0;
+/// The default number of requests that can be sent at once, the same as client-go's
+pub const DEFAULT_BURST: u32 = 10;
+
+/// Token bucket rate limiter. Clones share the bucket, so all clones of a `KubeClient` are
+/// limited together.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    qps: f64,
+    burst: u32,
+    bucket: Arc<Mutex<Bucket>>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    // Negative when requests are waiting for tokens
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl Default for RateLimiter {
+    fn default() -> RateLimiter {
+        RateLimiter::new(DEFAULT_QPS, DEFAULT_BURST)
+    }
+}
+
+impl RateLimiter {
+    /// A limiter allowing `qps` requests per second on average and bursts of up to `burst`
+    /// requests. The bucket starts out full. A `qps` that isn't positive, including NaN, doesn't
+    /// limit requests at all, like a negative QPS in client-go.
+    pub fn new(qps: f64, burst: u32) -> RateLimiter {
+        let qps = if qps > 0.0 { qps } else { f64::INFINITY };
+        let burst = burst.max(1);
+        RateLimiter {
+            qps,
+            burst,
+            bucket: Arc::new(Mutex::new(Bucket {
+                tokens: f64::from(burst),
+                last_refill: Instant::now(),
+            })),
+        }
+    }
+
+    pub fn qps(&self) -> f64 {
+        self.qps
+    }
+
+    pub fn burst(&self) -> u32 {
+        self.burst
+    }
+
+    /// Whether requests are let through without limit
+    pub fn is_unlimited(&self) -> bool {
+        self.qps.is_infinite()
+    }
+
+    /// Take a token without waiting, returns whether one was available
+    pub fn try_acquire(&self) -> bool {
+        if self.is_unlimited() {
+            return true;
+        }
+        let mut bucket = self.bucket.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        self.refill(&mut bucket);
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Take a token, blocking until one is available. Tokens are handed out in the order they
+    /// were asked for.
+    pub fn acquire(&self) {
+        if let Some(wait) = self.reserve() {
+            trace!("Rate limited, waiting {:?}", wait);
+            thread::sleep(wait);
+        }
+    }
+
+    // Reserve a token right away, returning how long to wait for it without holding the lock
+    fn reserve(&self) -> Option<Duration> {
+        if self.is_unlimited() {
+            return None;
+        }
+        let mut bucket = self.bucket.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        self.refill(&mut bucket);
+        bucket.tokens -= 1.0;
+        if bucket.tokens >= 0.0 {
+            None
+        } else {
+            Some(Duration::from_secs_f64(-bucket.tokens / self.qps))
+        }
+    }
+
+    fn refill(&self, bucket: &mut Bucket) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.qps).min(f64::from(self.burst));
+        bucket.last_refill = now;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Pretend the last refill happened `elapsed` ago
+    fn rewind(limiter: &RateLimiter, elapsed: Duration) {
+        let mut bucket = limiter.bucket.lock().unwrap();
+        bucket.last_refill -= elapsed;
+    }
+
+    #[test]
+    fn burst_and_refill() {
+        let limiter = RateLimiter::new(10.0, 2);
+        assert!(limiter.try_acquire());
+        assert!(limiter.try_acquire());
+        assert!(!limiter.try_acquire());
+        rewind(&limiter, Duration::from_millis(150));
+        assert!(limiter.try_acquire());
+        assert!(!limiter.try_acquire());
+        // The bucket never holds more than `burst` tokens
+        rewind(&limiter, Duration::from_secs(1));
+        assert!(limiter.try_acquire());
+        assert!(limiter.try_acquire());
+        assert!(!limiter.try_acquire());
+    }
+
+    #[test]
+    fn reservations_queue_up() {
+        let limiter = RateLimiter::new(10.0, 1);
+        assert_eq!(limiter.reserve(), None);
+        let first = limiter.reserve().unwrap();
+        let second = limiter.reserve().unwrap();
+        assert!(first > Duration::from_millis(90) && first <= Duration::from_millis(100));
+        assert!(second > Duration::from_millis(190) && second <= Duration::from_millis(200));
+        // Reserved tokens aren't available to `try_acquire`
+        rewind(&limiter, Duration::from_millis(150));
+        assert!(!limiter.try_acquire());
+    }
+
+    #[test]
+    fn non_positive_qps_is_unlimited() {
+        for &qps in &[0.0, -1.0, f64::NAN] {
+            let limiter = RateLimiter::new(qps, 1);
+            assert!(limiter.is_unlimited());
+            assert!((0..100).all(|_| limiter.try_acquire()));
+            assert_eq!(limiter.reserve(), None);
+        }
+        assert!(!RateLimiter::default().is_unlimited());
+    }
+}
@@ -0,0 +1,120 @@
+//! Retrying of operations that can fail transiently, e.g. updates that conflict with concurrent
+//! modifications.
+use std::thread;
+use std::time::Duration;
+
+use rand::{self, Rng};
+use reqwest::Method;
+
+/// Exponentially growing delays between attempts of an operation
+#[derive(Debug, Clone)]
+pub struct Backoff {
+    /// The number of attempts, including the first one
+    pub steps: u32,
+    /// The delay after the first attempt
+    pub duration: Duration,
+    /// The delay is multiplied by this after every attempt
+    pub factor: f64,
+    /// The longest delay between attempts
+    pub cap: Duration,
+}
+
+impl Default for Backoff {
+    /// Five attempts 10ms apart, like `retry.DefaultRetry` of client-go
+    fn default() -> Backoff {
+        Backoff {
+            steps: 5,
+            duration: Duration::from_millis(10),
+            factor: 1.0,
+            cap: Duration::from_secs(1),
+        }
+    }
+}
+
+impl Backoff {
+    /// The delay before the given retry, counting from 0 for the delay after the first attempt
+    pub fn delay(&self, retry: u32) -> Duration {
+        let delay = self.duration.as_secs_f64() * self.factor.powi(retry as i32);
+        Duration::from_secs_f64(delay.min(self.cap.as_secs_f64()))
+    }
+
+    /// Run `operation` until it succeeds, `should_retry` rejects its error or the attempts run
+    /// out, sleeping in between. The last result is returned.
+    pub fn retry<T, E, F, R>(&self, mut operation: F, should_retry: R) -> Result<T, E>
+        where F: FnMut() -> Result<T, E>,
+              R: Fn(&E) -> bool,
+    {
+        let mut retry = 0;
+        loop {
+            match operation() {
+                Err(ref error) if retry + 1 < self.steps && should_retry(error) => {
+                    thread::sleep(self.delay(retry));
+                    retry += 1;
+                },
+                result => return result,
+            }
+        }
+    }
+}
+
+/// When requests to the API server are sent again after failing transiently, i.e. because of
+/// transport errors, throttling (429 Too Many Requests) or an unavailable server (5xx).
+///
+/// Only idempotent requests (GET, HEAD, PUT, DELETE, OPTIONS) are retried unless
+/// `retry_non_idempotent` is set, as a POST or PATCH could have taken effect despite the error.
+/// A delay requested by the server with `Retry-After` is honored up to `backoff.cap`.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// The attempts of a request and the delays between them
+    pub backoff: Backoff,
+    /// Each delay is lengthened by a random fraction of itself, up to this factor
+    pub jitter: f64,
+    /// Whether POST and PATCH requests are retried as well
+    pub retry_non_idempotent: bool,
+}
+
+impl Default for RetryPolicy {
+    /// Up to 4 attempts, waiting 200ms, 400ms and 800ms plus up to 10% jitter in between
+    fn default() -> RetryPolicy {
+        RetryPolicy {
+            backoff: Backoff {
+                steps: 4,
+                duration: Duration::from_millis(200),
+                factor: 2.0,
+                cap: Duration::from_secs(10),
+            },
+            jitter: 0.1,
+            retry_non_idempotent: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Send every request exactly once
+    pub fn never() -> RetryPolicy {
+        RetryPolicy {
+            backoff: Backoff {
+                steps: 1,
+                ..Backoff::default()
+            },
+            ..RetryPolicy::default()
+        }
+    }
+
+    /// Whether requests with the given method may be retried
+    pub fn applies_to(&self, method: &Method) -> bool {
+        self.retry_non_idempotent ||
+            matches!(*method, Method::GET | Method::HEAD | Method::PUT | Method::DELETE | Method::OPTIONS)
+    }
+
+    /// The delay before the given retry, counting from 0, with jitter applied
+    pub fn delay(&self, retry: u32) -> Duration {
+        let delay = self.backoff.delay(retry);
+        if self.jitter > 0.0 {
+            let jitter = rand::thread_rng().gen_range(0.0, self.jitter);
+            delay + Duration::from_secs_f64(delay.as_secs_f64() * jitter)
+        } else {
+            delay
+        }
+    }
+}
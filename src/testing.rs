@@ -0,0 +1,652 @@
+//! An in-memory fake of the API server for testing code that uses `KubeClient`.
+//!
+//! The server speaks plain HTTP on a random local port and implements the REST semantics of any
+//! resource, built-in or custom, without knowing about it in advance: objects are created, read,
+//! listed, replaced, patched and deleted under the usual paths, every write bumps the
+//! `resourceVersion`, and collections can be filtered by label selectors and watched.
+//!
+//! Objects get the `apiVersion` of their path and keep the `kind` they were created with. As the
+//! server doesn't know the resources, objects created without a kind get the kind of earlier
+//! objects of the resource, or one guessed from the resource name, e.g. `Pod` for `pods`.
+//!
+//! Some things are deliberately left out: subresources, field selectors, pagination, validation,
+//! defaulting and garbage collection. Strategic merge patches are applied as JSON merge patches.
+//!
+//! ```no_run
+//! # use kubeclient::testing::FakeApiServer;
+//! # use kubeclient::api::core::v1::Pod;
+//! let server = FakeApiServer::start().unwrap();
+//! let client = server.client().unwrap();
+//! let pods = client.list_namespaced_resource::<Pod>("default").unwrap();
+//! assert!(pods.items.is_empty());
+//! ```
+use std::collections::{BTreeMap, HashMap};
+use std::io::{Error as IoError, ErrorKind, Write};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use chrono::{SecondsFormat, Utc};
+use serde_json::{self, json, Value};
+use tiny_http::{Header, Method, Request, Response, Server};
+use uuid::Uuid;
+
+use crate::apimachinery::apis::meta;
+use crate::apimachinery::labels::Selector;
+use crate::client::{ClientInitError, KubeClient, KubeClientBuilder};
+use crate::config::{AuthConfig, ClientConfig};
+use crate::json_patch::{self, Patch};
+use crate::retry::RetryPolicy;
+
+// How often blocked threads check whether the server was stopped
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// A running fake API server. It's stopped when this is dropped, ending any open watches.
+pub struct FakeApiServer {
+    url: String,
+    shared: Arc<Shared>,
+}
+
+struct Shared {
+    store: Mutex<Store>,
+    // Notified on every write, for watches
+    changed: Condvar,
+    stopped: AtomicBool,
+}
+
+#[derive(Default)]
+struct Store {
+    resource_version: u64,
+    objects: BTreeMap<ObjectKey, Value>,
+    events: Vec<Event>,
+    // The kinds of the resources, learned from the objects written to them
+    kinds: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct ObjectKey {
+    // The collection path without the namespace, e.g. "apis/apps/v1/deployments"
+    resource: String,
+    // Empty for cluster-scoped objects
+    namespace: String,
+    name: String,
+}
+
+struct Event {
+    resource_version: u64,
+    key: ObjectKey,
+    type_: &'static str,
+    object: Value,
+}
+
+// The collection or object a request is for
+struct ResourcePath {
+    resource: String,
+    namespace: Option<String>,
+    name: Option<String>,
+}
+
+// The objects a list or watch is for
+struct Filter {
+    resource: String,
+    namespace: Option<String>,
+    selector: Selector,
+}
+
+impl Filter {
+    fn matches(&self, key: &ObjectKey, object: &Value) -> bool {
+        key.resource == self.resource
+            && self.namespace.iter().all(|namespace| *namespace == key.namespace)
+            && self.selector.matches(&labels(object))
+    }
+}
+
+enum Reply {
+    Json(u16, Value),
+    Watch(Filter, Option<u64>, Option<Instant>),
+}
+
+impl FakeApiServer {
+    /// Start a server on a random port of localhost, serving requests on background threads
+    pub fn start() -> Result<FakeApiServer, IoError> {
+        let server = Server::http("127.0.0.1:0").map_err(|error| IoError::new(ErrorKind::Other, error))?;
+        let url = format!("http://{}", server.server_addr());
+        let shared = Arc::new(Shared {
+            store: Mutex::new(Store::default()),
+            changed: Condvar::new(),
+            stopped: AtomicBool::new(false),
+        });
+        let server_shared = shared.clone();
+        thread::spawn(move || {
+            while !server_shared.stopped.load(Ordering::SeqCst) {
+                let request = match server.recv_timeout(POLL_INTERVAL) {
+                    Ok(Some(request)) => request,
+                    Ok(None) => continue,
+                    Err(error) => {
+                        warn!("Fake API server failed to receive a request: {}", error);
+                        break;
+                    },
+                };
+                let shared = server_shared.clone();
+                thread::spawn(move || {
+                    if let Err(error) = shared.respond(request) {
+                        debug!("Fake API server failed to respond: {}", error);
+                    }
+                });
+            }
+        });
+        Ok(FakeApiServer { url, shared })
+    }
+
+    /// The base URL of the server, e.g. `http://127.0.0.1:34567`
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// A client for this server, which neither retries nor rate limits requests
+    pub fn client(&self) -> Result<KubeClient, ClientInitError> {
+        let config = ClientConfig::External {
+            api_url: self.url.clone(),
+            auth_info: AuthConfig::Token("fake".to_string()),
+            ca: None,
+        };
+        Ok(KubeClientBuilder::new(config).build()?
+            .with_retry_policy(RetryPolicy::never())
+            .without_rate_limit())
+    }
+
+    /// The current resource version, which is bumped by every write
+    pub fn resource_version(&self) -> u64 {
+        self.shared.lock().resource_version
+    }
+}
+
+impl Drop for FakeApiServer {
+    fn drop(&mut self) {
+        self.shared.stopped.store(true, Ordering::SeqCst);
+        self.shared.changed.notify_all();
+    }
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, Store> {
+        self.store.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn respond(&self, mut request: Request) -> Result<(), IoError> {
+        let url = request.url().to_string();
+        let mut parts = url.splitn(2, '?');
+        let path = parts.next().unwrap_or("").to_string();
+        let query = parse_query(parts.next().unwrap_or(""));
+        let content_type = request.headers().iter()
+            .find(|header| header.field.equiv("Content-Type"))
+            .map(|header| header.value.as_str().to_string())
+            .unwrap_or_default();
+        let mut body = Vec::new();
+        request.as_reader().read_to_end(&mut body)?;
+
+        let reply = self.handle(request.method(), &path, &query, &content_type, &body);
+        if *request.method() != Method::Get {
+            self.changed.notify_all();
+        }
+        match reply {
+            Reply::Json(code, value) => {
+                let body = serde_json::to_vec(&value).map_err(IoError::from)?;
+                request.respond(Response::from_data(body).with_status_code(code).with_header(json_header()))
+            },
+            Reply::Watch(filter, resource_version, deadline) =>
+                self.watch(request.into_writer(), &filter, resource_version, deadline),
+        }
+    }
+
+    fn handle(&self, method: &Method, path: &str, query: &HashMap<String, String>, content_type: &str, body: &[u8]) -> Reply {
+        let path = match parse_path(path) {
+            Some(path) => path,
+            None => return error_reply(404, "NotFound", format!("the server could not find the requested resource ({})", path)),
+        };
+        let name = match path.name {
+            Some(ref name) => name.clone(),
+            None => return match *method {
+                Method::Get => self.list_or_watch(path, query),
+                Method::Post => match serde_json::from_slice(body) {
+                    Ok(object) => self.create(&path, object),
+                    Err(error) => error_reply(400, "BadRequest", error.to_string()),
+                },
+                _ => error_reply(405, "MethodNotAllowed", format!("{} is not supported on collections", method)),
+            },
+        };
+        let key = ObjectKey {
+            resource: path.resource.clone(),
+            namespace: path.namespace.clone().unwrap_or_default(),
+            name,
+        };
+        match *method {
+            Method::Get => match self.lock().objects.get(&key) {
+                Some(object) => Reply::Json(200, object.clone()),
+                None => not_found(&key),
+            },
+            Method::Put => match serde_json::from_slice(body) {
+                Ok(object) => self.replace(&key, object),
+                Err(error) => error_reply(400, "BadRequest", error.to_string()),
+            },
+            Method::Patch => self.patch(&key, content_type, body),
+            Method::Delete => self.delete(&key),
+            _ => error_reply(405, "MethodNotAllowed", format!("{} is not supported on objects", method)),
+        }
+    }
+
+    fn list_or_watch(&self, path: ResourcePath, query: &HashMap<String, String>) -> Reply {
+        if matches!(query.get("fieldSelector"), Some(selector) if !selector.is_empty()) {
+            return error_reply(400, "BadRequest", "field selectors are not supported by the fake API server".to_string());
+        }
+        let selector = match query.get("labelSelector").map(|selector| selector.parse()) {
+            None => Selector::default(),
+            Some(Ok(selector)) => selector,
+            Some(Err(error)) => return error_reply(400, "BadRequest", format!("invalid label selector: {}", error)),
+        };
+        let filter = Filter {
+            resource: path.resource,
+            namespace: path.namespace,
+            selector,
+        };
+        if matches!(query.get("watch").map(String::as_str), Some("1") | Some("true")) {
+            let resource_version = query.get("resourceVersion")
+                .and_then(|version| version.parse().ok())
+                .filter(|version| *version > 0);
+            let deadline = query.get("timeoutSeconds")
+                .and_then(|seconds| seconds.parse().ok())
+                .map(|seconds| Instant::now() + Duration::from_secs(seconds));
+            return Reply::Watch(filter, resource_version, deadline);
+        }
+
+        let store = self.lock();
+        let items: Vec<Value> = store.objects.iter()
+            .filter(|(key, object)| filter.matches(key, object))
+            .map(|(_, object)| object.clone())
+            .collect();
+        Reply::Json(200, json!({
+            "kind": format!("{}List", store.kind(&filter.resource)),
+            "apiVersion": api_version(&filter.resource),
+            "metadata": { "resourceVersion": store.resource_version.to_string() },
+            "items": items,
+        }))
+    }
+
+    fn create(&self, path: &ResourcePath, mut object: Value) -> Reply {
+        if !object.is_object() {
+            return error_reply(400, "BadRequest", "the object must be a JSON object".to_string());
+        }
+        let namespace = path.namespace.clone().unwrap_or_default();
+        let mut name = string_field(&object["metadata"], "name");
+        if name.is_empty() {
+            let generate_name = string_field(&object["metadata"], "generateName");
+            if generate_name.is_empty() {
+                return error_reply(422, "Invalid", "metadata.name or metadata.generateName is required".to_string());
+            }
+            name = format!("{}{}", generate_name, &Uuid::new_v4().to_simple().to_string()[..5]);
+        }
+        let body_namespace = string_field(&object["metadata"], "namespace");
+        if !body_namespace.is_empty() && body_namespace != namespace {
+            return error_reply(400, "BadRequest", "the namespace of the object does not match the namespace of the request".to_string());
+        }
+
+        let key = ObjectKey { resource: path.resource.clone(), namespace, name };
+        let mut store = self.lock();
+        if store.objects.contains_key(&key) {
+            return error_reply_for(&key, 409, "AlreadyExists", format!("{} \"{}\" already exists", key.resource, key.name));
+        }
+        {
+            let metadata = metadata_mut(&mut object);
+            metadata.insert("name".to_string(), Value::String(key.name.clone()));
+            if !key.namespace.is_empty() {
+                metadata.insert("namespace".to_string(), Value::String(key.namespace.clone()));
+            }
+            metadata.insert("uid".to_string(), Value::String(Uuid::new_v4().to_string()));
+            metadata.insert("creationTimestamp".to_string(),
+                            Value::String(Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true)));
+            metadata.insert("generation".to_string(), json!(1));
+        }
+        store.set_type_meta(&key.resource, &mut object);
+        let object = store.write(key, "ADDED", object);
+        Reply::Json(201, object)
+    }
+
+    fn replace(&self, key: &ObjectKey, object: Value) -> Reply {
+        self.lock().replace(key, object)
+    }
+
+    fn patch(&self, key: &ObjectKey, content_type: &str, body: &[u8]) -> Reply {
+        let mut store = self.lock();
+        let mut object = match store.objects.get(key) {
+            Some(object) => object.clone(),
+            None => return not_found(key),
+        };
+        let content_type = content_type.split(';').next().unwrap_or("").trim();
+        let result = match content_type {
+            "application/json-patch+json" => serde_json::from_slice::<Patch>(body)
+                .map_err(|error| error.to_string())
+                .and_then(|patch| json_patch::apply(&mut object, &patch).map_err(|error| format!("{:?}", error))),
+            "application/merge-patch+json" | "application/strategic-merge-patch+json" => serde_json::from_slice(body)
+                .map(|patch| merge_patch(&mut object, &patch))
+                .map_err(|error| error.to_string()),
+            _ => return error_reply(415, "UnsupportedMediaType", format!("unsupported patch type {}", content_type)),
+        };
+        if let Err(error) = result {
+            return error_reply(422, "Invalid", error);
+        }
+        // A resourceVersion in a merge patch is a precondition, otherwise the patch applies to the
+        // current object unconditionally
+        let precondition = serde_json::from_slice::<Value>(body).ok()
+            .map(|patch| string_field(&patch["metadata"], "resourceVersion"))
+            .unwrap_or_default();
+        if precondition.is_empty() {
+            metadata_mut(&mut object).remove("resourceVersion");
+        }
+        store.replace(key, object)
+    }
+
+    fn delete(&self, key: &ObjectKey) -> Reply {
+        let mut store = self.lock();
+        let object = match store.objects.remove(key) {
+            Some(object) => object,
+            None => return not_found(key),
+        };
+        let object = store.write(key.clone(), "DELETED", object);
+        // Writing stored the object again
+        store.objects.remove(key);
+        Reply::Json(200, object)
+    }
+
+    // Stream the events matching the filter as a chunked response. Without a resource version
+    // the current objects are sent as ADDED events first, like the API server does.
+    fn watch(&self, mut writer: Box<dyn Write + Send>, filter: &Filter, resource_version: Option<u64>,
+             deadline: Option<Instant>) -> Result<(), IoError> {
+        writer.write_all(b"HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nTransfer-Encoding: chunked\r\nConnection: close\r\n\r\n")?;
+        writer.flush()?;
+
+        let mut last_version = match resource_version {
+            Some(resource_version) => resource_version,
+            None => {
+                let store = self.lock();
+                for (_, object) in store.objects.iter().filter(|(key, object)| filter.matches(key, object)) {
+                    write_chunk(&mut writer, &json!({ "type": "ADDED", "object": object }))?;
+                }
+                store.resource_version
+            },
+        };
+        loop {
+            let events: Vec<Value> = {
+                let mut store = self.lock();
+                loop {
+                    if self.stopped.load(Ordering::SeqCst) || matches!(deadline, Some(deadline) if Instant::now() >= deadline) {
+                        return writer.write_all(b"0\r\n\r\n").and_then(|_| writer.flush());
+                    }
+                    if store.resource_version > last_version {
+                        break;
+                    }
+                    store = self.changed.wait_timeout(store, POLL_INTERVAL)
+                        .unwrap_or_else(|poisoned| poisoned.into_inner()).0;
+                }
+                let events = store.events.iter()
+                    .filter(|event| event.resource_version > last_version && filter.matches(&event.key, &event.object))
+                    .map(|event| json!({ "type": event.type_, "object": event.object }))
+                    .collect();
+                last_version = store.resource_version;
+                events
+            };
+            for event in &events {
+                write_chunk(&mut writer, event)?;
+            }
+        }
+    }
+}
+
+impl Store {
+    fn replace(&mut self, key: &ObjectKey, mut object: Value) -> Reply {
+        if !object.is_object() {
+            return error_reply(400, "BadRequest", "the object must be a JSON object".to_string());
+        }
+        let name = string_field(&object["metadata"], "name");
+        if !name.is_empty() && name != key.name {
+            return error_reply(400, "BadRequest", "the name of the object does not match the name of the request".to_string());
+        }
+        let current = match self.objects.get(key) {
+            Some(current) => current.clone(),
+            None => return not_found(key),
+        };
+        let resource_version = string_field(&object["metadata"], "resourceVersion");
+        if !resource_version.is_empty() && resource_version != string_field(&current["metadata"], "resourceVersion") {
+            return error_reply_for(key, 409, "Conflict", format!(
+                "Operation cannot be fulfilled on {} \"{}\": the object has been modified; please apply your changes to the latest version and try again",
+                key.resource, key.name));
+        }
+        let spec_changed = object["spec"] != current["spec"];
+        {
+            let metadata = metadata_mut(&mut object);
+            metadata.insert("name".to_string(), Value::String(key.name.clone()));
+            if !key.namespace.is_empty() {
+                metadata.insert("namespace".to_string(), Value::String(key.namespace.clone()));
+            }
+            // Fields set by the server can't be changed
+            for field in &["uid", "creationTimestamp", "generation"] {
+                match current["metadata"].get(*field) {
+                    Some(value) => metadata.insert(field.to_string(), value.clone()),
+                    None => metadata.remove(*field),
+                };
+            }
+            if spec_changed {
+                let generation = current["metadata"]["generation"].as_i64().unwrap_or(0) + 1;
+                metadata.insert("generation".to_string(), json!(generation));
+            }
+        }
+        self.set_type_meta(&key.resource, &mut object);
+        let object = self.write(key.clone(), "MODIFIED", object);
+        Reply::Json(200, object)
+    }
+
+    // Set the kind and API version like the API server does, learning the kind of the resource
+    // from the object if it has one
+    fn set_type_meta(&mut self, resource: &str, object: &mut Value) {
+        let kind = match string_field(object, "kind") {
+            kind if kind.is_empty() => self.kind(resource),
+            kind => {
+                self.kinds.insert(resource.to_string(), kind.clone());
+                kind
+            },
+        };
+        let object = object.as_object_mut().expect("objects are checked to be JSON objects");
+        object.insert("kind".to_string(), Value::String(kind));
+        object.insert("apiVersion".to_string(), Value::String(api_version(resource)));
+    }
+
+    fn kind(&self, resource: &str) -> String {
+        self.kinds.get(resource).cloned().unwrap_or_else(|| guess_kind(resource))
+    }
+
+    // Store an object with a new resource version and record the event, returning the object
+    fn write(&mut self, key: ObjectKey, type_: &'static str, mut object: Value) -> Value {
+        self.resource_version += 1;
+        metadata_mut(&mut object).insert("resourceVersion".to_string(), Value::String(self.resource_version.to_string()));
+        self.objects.insert(key.clone(), object.clone());
+        self.events.push(Event {
+            resource_version: self.resource_version,
+            key,
+            type_,
+            object: object.clone(),
+        });
+        object
+    }
+}
+
+fn write_chunk(writer: &mut Box<dyn Write + Send>, value: &Value) -> Result<(), IoError> {
+    let mut data = serde_json::to_vec(value).map_err(IoError::from)?;
+    data.push(b'\n');
+    write!(writer, "{:x}\r\n", data.len())?;
+    writer.write_all(&data)?;
+    writer.write_all(b"\r\n")?;
+    writer.flush()
+}
+
+// Split /api/v1/namespaces/default/pods/name and /apis/apps/v1/deployments into their parts
+fn parse_path(path: &str) -> Option<ResourcePath> {
+    let segments: Vec<&str> = path.split('/').filter(|segment| !segment.is_empty()).collect();
+    let prefix = match segments.first() {
+        Some(&"api") => 2,
+        Some(&"apis") => 3,
+        _ => return None,
+    };
+    if segments.len() <= prefix {
+        return None;
+    }
+    let rest = &segments[prefix..];
+    let (namespace, rest) = if rest.len() >= 3 && rest[0] == "namespaces" {
+        (Some(rest[1].to_string()), &rest[2..])
+    } else {
+        (None, rest)
+    };
+    // Subresources aren't supported
+    if rest.len() > 2 {
+        return None;
+    }
+    Some(ResourcePath {
+        resource: format!("{}/{}", segments[..prefix].join("/"), rest[0]),
+        namespace,
+        name: rest.get(1).map(|name| name.to_string()),
+    })
+}
+
+// "api/v1/pods" is in "v1", "apis/apps/v1/deployments" in "apps/v1"
+fn api_version(resource: &str) -> String {
+    let mut segments: Vec<&str> = resource.split('/').collect();
+    segments.pop();
+    segments.remove(0);
+    segments.join("/")
+}
+
+// The singular of the resource name, capitalized: "apis/apps/v1/deployments" holds Deployments,
+// "api/v1/endpoints" is guessed wrong
+fn guess_kind(resource: &str) -> String {
+    let plural = resource.rsplit('/').next().unwrap_or("");
+    let singular = if let Some(stem) = plural.strip_suffix("ies") {
+        format!("{}y", stem)
+    } else if ["sses", "xes", "zes", "ches", "shes"].iter().any(|suffix| plural.ends_with(suffix)) {
+        plural[..plural.len() - 2].to_string()
+    } else {
+        plural.strip_suffix('s').unwrap_or(plural).to_string()
+    };
+    let mut chars = singular.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => singular,
+    }
+}
+
+fn parse_query(query: &str) -> HashMap<String, String> {
+    query.split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let mut parts = pair.splitn(2, '=');
+            (percent_decode(parts.next().unwrap_or("")), percent_decode(parts.next().unwrap_or("")))
+        })
+        .collect()
+}
+
+fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' => match (bytes.get(i + 1).and_then(hex_digit), bytes.get(i + 2).and_then(hex_digit)) {
+                (Some(high), Some(low)) => {
+                    decoded.push(high << 4 | low);
+                    i += 2;
+                },
+                _ => decoded.push(b'%'),
+            },
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn hex_digit(byte: &u8) -> Option<u8> {
+    (*byte as char).to_digit(16).map(|digit| digit as u8)
+}
+
+// Apply a JSON merge patch (RFC 7386)
+fn merge_patch(target: &mut Value, patch: &Value) {
+    match patch {
+        Value::Object(patch) => {
+            if !target.is_object() {
+                *target = json!({});
+            }
+            let target = target.as_object_mut().expect("made an object above");
+            for (key, value) in patch {
+                if value.is_null() {
+                    target.remove(key);
+                } else {
+                    merge_patch(target.entry(key.clone()).or_insert(Value::Null), value);
+                }
+            }
+        },
+        _ => *target = patch.clone(),
+    }
+}
+
+fn metadata_mut(object: &mut Value) -> &mut serde_json::Map<String, Value> {
+    let object = object.as_object_mut().expect("objects are checked to be JSON objects");
+    let metadata = object.entry("metadata").or_insert_with(|| json!({}));
+    if !metadata.is_object() {
+        *metadata = json!({});
+    }
+    metadata.as_object_mut().expect("made an object above")
+}
+
+fn string_field(value: &Value, field: &str) -> String {
+    value.get(field).and_then(Value::as_str).unwrap_or("").to_string()
+}
+
+fn labels(object: &Value) -> HashMap<String, String> {
+    serde_json::from_value(object["metadata"]["labels"].clone()).unwrap_or_default()
+}
+
+fn json_header() -> Header {
+    Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..]).expect("static header is valid")
+}
+
+fn status(code: u16, reason: &str, message: String, details: Option<meta::v1::StatusDetails>) -> Value {
+    let status = meta::v1::Status {
+        type_meta: meta::v1::TypeMeta {
+            kind: "Status".to_string(),
+            api_version: "v1".to_string(),
+        },
+        metadata: None,
+        status: "Failure".to_string(),
+        message,
+        reason: reason.to_string(),
+        details,
+        code: i32::from(code),
+    };
+    serde_json::to_value(status).expect("Status can be serialized")
+}
+
+fn error_reply(code: u16, reason: &str, message: String) -> Reply {
+    Reply::Json(code, status(code, reason, message, None))
+}
+
+fn error_reply_for(key: &ObjectKey, code: u16, reason: &str, message: String) -> Reply {
+    let details = meta::v1::StatusDetails {
+        name: key.name.clone(),
+        kind: key.resource.rsplit('/').next().unwrap_or("").to_string(),
+        ..Default::default()
+    };
+    Reply::Json(code, status(code, reason, message, Some(details)))
+}
+
+fn not_found(key: &ObjectKey) -> Reply {
+    error_reply_for(key, 404, "NotFound", format!("{} \"{}\" not found", key.resource, key.name))
+}
@@ -0,0 +1,299 @@
+//! A small HTTPS server for admission webhooks.
+//!
+//! Handlers are registered per URL path, either as validating or as mutating webhooks. The server
+//! takes care of decoding the incoming `AdmissionReview`, copying the request UID into the
+//! response and encoding the patch returned by mutating handlers. Mutating handlers can produce
+//! their patch with `json_patch::diff` from the request's object and a mutated copy of it.
+//!
+//! Handlers always receive `admission.k8s.io/v1` requests. Reviews sent as `v1beta1` are converted
+//! before being passed to the handler, and the response is sent back in the version of the request.
+use std::fmt;
+use std::collections::HashMap;
+use std::error::Error as StdError;
+use std::io::Error as IoError;
+use std::net::ToSocketAddrs;
+use std::sync::Arc;
+use std::thread;
+
+use serde::de::DeserializeOwned;
+use serde_json::{self, Value};
+use tiny_http::{Header, Method, Request, Response, Server, SslConfig};
+
+use crate::api::admission::{v1beta1, API_GROUP};
+use crate::api::admission::v1::{AdmissionReview, AdmissionRequest, AdmissionResponse};
+use crate::apimachinery::apis::meta;
+use crate::json_patch::Patch;
+
+/// Result of a validating handler, either allowing the request or rejecting it with a status
+pub type ValidationResult = Result<(), meta::v1::Status>;
+
+/// Result of a mutating handler, the JSON Patch to apply to the object or a status rejecting the
+/// request. An empty patch admits the object unchanged.
+pub type MutationResult = Result<Patch, meta::v1::Status>;
+
+// A handler with its object type erased, taking the raw request body and producing the response
+// review
+type Handler = Box<dyn Fn(&[u8]) -> Result<Value, serde_json::Error> + Send + Sync>;
+
+/// Errors that can occur while running the webhook server
+#[derive(Debug)]
+pub enum WebhookError {
+    /// The server could not be started, e.g. because the certificate was invalid
+    ServerError(Box<dyn StdError + Send + Sync>),
+    /// IO error while handling a request
+    IoError(IoError),
+}
+
+impl fmt::Display for WebhookError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WebhookError::ServerError(error) => write!(f, "Unable to start webhook server: {}", error),
+            WebhookError::IoError(error) => write!(f, "IO error in webhook server: {}", error),
+        }
+    }
+}
+
+impl StdError for WebhookError {}
+
+/// An admission webhook server that routes request paths to handlers
+#[derive(Default)]
+pub struct WebhookServer {
+    handlers: HashMap<String, Handler>,
+}
+
+impl WebhookServer {
+    pub fn new() -> WebhookServer {
+        WebhookServer::default()
+    }
+
+    /// Register a validating webhook on `path`
+    pub fn validating<T, F>(self, path: &str, handler: F) -> WebhookServer
+        where T: DeserializeOwned + 'static,
+              F: Fn(&AdmissionRequest<T>) -> ValidationResult + Send + Sync + 'static
+    {
+        self.mutating(path, move |request: &AdmissionRequest<T>| handler(request).map(|_| Vec::new()))
+    }
+
+    /// Register a mutating webhook on `path`
+    pub fn mutating<T, F>(mut self, path: &str, handler: F) -> WebhookServer
+        where T: DeserializeOwned + 'static,
+              F: Fn(&AdmissionRequest<T>) -> MutationResult + Send + Sync + 'static
+    {
+        let handler = move |body: &[u8]| {
+            let type_meta: meta::v1::TypeMeta = serde_json::from_slice(body)?;
+            if type_meta.api_version == format!("{}/{}", API_GROUP, v1beta1::API_VERSION) {
+                let review: v1beta1::AdmissionReview<T> = serde_json::from_slice(body)?;
+                let response = review_response(&handler, review.into());
+                serde_json::to_value(v1beta1::AdmissionReview::<Value>::from(response))
+            } else {
+                let review: AdmissionReview<T> = serde_json::from_slice(body)?;
+                serde_json::to_value(review_response(&handler, review))
+            }
+        };
+        self.handlers.insert(path.to_string(), Box::new(handler));
+        self
+    }
+
+    /// Handle a single admission review sent to `path`, returning the review to respond with.
+    /// Returns `None` if there's no handler registered for the path.
+    pub fn handle(&self, path: &str, body: &[u8]) -> Option<Result<Value, serde_json::Error>> {
+        self.handlers.get(path).map(|handler| handler(body))
+    }
+
+    /// Serve the registered webhooks over HTTPS on the given address.
+    /// `certificate` and `private_key` are expected to be PEM encoded.
+    /// Each request is handled on its own thread, this method only returns if the server fails.
+    pub fn serve<A: ToSocketAddrs>(self, addr: A, certificate: Vec<u8>, private_key: Vec<u8>) -> Result<(), WebhookError> {
+        let server = Server::https(addr, SslConfig { certificate, private_key })
+                            .map_err(WebhookError::ServerError)?;
+        info!("Admission webhook server listening on {}", server.server_addr());
+        let webhooks = Arc::new(self);
+        loop {
+            let request = server.recv().map_err(WebhookError::IoError)?;
+            let webhooks = webhooks.clone();
+            thread::spawn(move || {
+                if let Err(error) = webhooks.respond(request) {
+                    warn!("Failed to respond to admission request: {}", error);
+                }
+            });
+        }
+    }
+
+    fn respond(&self, mut request: Request) -> Result<(), IoError> {
+        if *request.method() != Method::Post {
+            return request.respond(Response::empty(405));
+        }
+        // Ignore any query parameters when routing
+        let path = request.url().split('?').next().unwrap_or("").to_string();
+        let mut body = Vec::new();
+        request.as_reader().read_to_end(&mut body)?;
+        match self.handle(&path, &body) {
+            None => request.respond(Response::empty(404)),
+            Some(Err(error)) => {
+                debug!("Unable to decode AdmissionReview sent to {}: {}", path, error);
+                request.respond(Response::from_string(error.to_string()).with_status_code(400))
+            },
+            Some(Ok(review)) => {
+                let body = serde_json::to_vec(&review).map_err(IoError::from)?;
+                let content_type = Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..])
+                                          .expect("static header is valid");
+                request.respond(Response::from_data(body).with_header(content_type))
+            },
+        }
+    }
+}
+
+fn review_response<T, F>(handler: &F, review: AdmissionReview<T>) -> AdmissionReview<Value>
+    where F: Fn(&AdmissionRequest<T>) -> MutationResult
+{
+    let response = match review.request {
+        Some(ref request) => admission_response(request, handler(request)),
+        // Nothing to review, the API server never sends this
+        None => AdmissionResponse {
+            uid: Default::default(),
+            allowed: false,
+            status: Some(rejection(meta::v1::Status {
+                message: "AdmissionReview did not contain a request".to_string(),
+                code: 400,
+                ..Default::default()
+            })),
+            patch: String::new(),
+            patch_type: None,
+            audit_annotations: Default::default(),
+            warnings: Vec::new(),
+        },
+    };
+    AdmissionReview {
+        type_meta: review.type_meta,
+        request: None,
+        response: Some(response),
+    }
+}
+
+fn admission_response<T>(request: &AdmissionRequest<T>, result: MutationResult) -> AdmissionResponse {
+    match result {
+        Ok(patch) => {
+            let mut response = AdmissionResponse {
+                uid: request.uid,
+                allowed: true,
+                status: None,
+                patch: String::new(),
+                patch_type: None,
+                audit_annotations: Default::default(),
+                warnings: Vec::new(),
+            };
+            response.set_patch(&patch);
+            response
+        },
+        Err(status) => AdmissionResponse {
+            uid: request.uid,
+            allowed: false,
+            status: Some(rejection(status)),
+            patch: String::new(),
+            patch_type: None,
+            audit_annotations: Default::default(),
+            warnings: Vec::new(),
+        },
+    }
+}
+
+// Fill in the fields of a rejection the handler left empty
+fn rejection(mut status: meta::v1::Status) -> meta::v1::Status {
+    if status.status.is_empty() {
+        status.status = "Failure".to_string();
+    }
+    if status.code == 0 {
+        status.code = 403;
+    }
+    status
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    use crate::api::core::v1::ConfigMap;
+    use crate::json_patch::PatchOperation;
+
+    const UID: &str = "705ab4f5-6393-11e8-b7cc-42010a800002";
+
+    fn review(api_version: &str, value: &str) -> Vec<u8> {
+        serde_json::to_vec(&json!({
+            "apiVersion": api_version,
+            "kind": "AdmissionReview",
+            "request": {
+                "uid": UID,
+                "kind": { "group": "", "version": "v1", "kind": "ConfigMap" },
+                "resource": { "group": "", "version": "v1", "resource": "configmaps" },
+                "namespace": "default",
+                "operation": "CREATE",
+                "userInfo": { "username": "admin" },
+                "object": {
+                    "apiVersion": "v1",
+                    "kind": "ConfigMap",
+                    "metadata": { "name": "settings", "namespace": "default" },
+                    "data": { "value": value },
+                },
+            },
+        })).unwrap()
+    }
+
+    fn server() -> WebhookServer {
+        WebhookServer::new()
+            .validating("/validate", |request: &AdmissionRequest<ConfigMap>| {
+                match request.object {
+                    Some(ref config_map) if config_map.data.contains_key("value") => Ok(()),
+                    _ => Err(meta::v1::Status { message: "missing value".to_string(), ..Default::default() }),
+                }
+            })
+            .mutating("/mutate", |request: &AdmissionRequest<ConfigMap>| {
+                let mut config_map: Value = serde_json::to_value(&request.object).unwrap();
+                config_map["data"]["mutated"] = json!("true");
+                Ok(crate::json_patch::diff_values(&serde_json::to_value(&request.object).unwrap(), &config_map))
+            })
+    }
+
+    #[test]
+    fn validating() {
+        let server = server();
+        let response = server.handle("/validate", &review("admission.k8s.io/v1", "a")).unwrap().unwrap();
+        assert_eq!(response["response"]["uid"], UID);
+        assert_eq!(response["response"]["allowed"], true);
+        assert!(response["response"].get("patch").is_none());
+
+        let mut body: Value = serde_json::from_slice(&review("admission.k8s.io/v1", "a")).unwrap();
+        body["request"]["object"]["data"] = json!({});
+        let response = server.handle("/validate", &serde_json::to_vec(&body).unwrap()).unwrap().unwrap();
+        assert_eq!(response["response"]["uid"], UID);
+        assert_eq!(response["response"]["allowed"], false);
+        assert_eq!(response["response"]["status"]["message"], "missing value");
+        assert_eq!(response["response"]["status"]["code"], 403);
+    }
+
+    #[test]
+    fn mutating() {
+        let response = server().handle("/mutate", &review("admission.k8s.io/v1", "a")).unwrap().unwrap();
+        assert_eq!(response["response"]["uid"], UID);
+        assert_eq!(response["response"]["allowed"], true);
+        let patch = base64::decode(response["response"]["patch"].as_str().unwrap()).unwrap();
+        let patch: Vec<PatchOperation> = serde_json::from_slice(&patch).unwrap();
+        assert_eq!(patch, vec![PatchOperation::Add { path: "/data/mutated".to_string(), value: json!("true") }]);
+    }
+
+    #[test]
+    fn review_versions() {
+        for api_version in &["admission.k8s.io/v1", "admission.k8s.io/v1beta1"] {
+            let response = server().handle("/mutate", &review(api_version, "a")).unwrap().unwrap();
+            assert_eq!(response["apiVersion"], *api_version);
+            assert_eq!(response["kind"], "AdmissionReview");
+            assert_eq!(response["response"]["uid"], UID);
+            assert_eq!(response["response"]["patchType"], "JSONPatch");
+        }
+    }
+
+    #[test]
+    fn unknown_path() {
+        assert!(server().handle("/other", &review("admission.k8s.io/v1", "a")).is_none());
+    }
+}
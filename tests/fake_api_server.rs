@@ -0,0 +1,117 @@
+#![cfg(feature = "testing")]
+use serde_json::json;
+
+use kubeclient::api::apps::v1::Deployment;
+use kubeclient::api::core::v1::ConfigMap;
+use kubeclient::apimachinery::apis::meta::v1::WatchEvent;
+use kubeclient::testing::FakeApiServer;
+
+fn config_map(name: &str, value: &str) -> ConfigMap {
+    let mut config_map = ConfigMap::new(name);
+    config_map.data.insert("value".to_string(), value.to_string());
+    config_map
+}
+
+#[test]
+fn create_get_and_list() {
+    let server = FakeApiServer::start().unwrap();
+    let client = server.client().unwrap();
+
+    let created = client.create_namespaced_resource("default", &config_map("settings", "a")).unwrap();
+    assert_eq!(created.metadata.namespace, "default");
+    assert!(!created.metadata.uid.is_nil());
+    assert_eq!(created.metadata.resource_version, server.resource_version().to_string());
+    client.create_namespaced_resource("other", &config_map("settings", "b")).unwrap();
+
+    let fetched: ConfigMap = client.get_namespaced_resource("default", "settings").unwrap();
+    assert_eq!(fetched.data["value"], "a");
+    assert_eq!(fetched.metadata.uid, created.metadata.uid);
+
+    let list = client.list_namespaced_resource::<ConfigMap>("default").unwrap();
+    assert_eq!(list.type_meta.kind, "ConfigMapList");
+    assert_eq!(list.type_meta.api_version, "v1");
+    assert_eq!(list.items.len(), 1);
+    assert_eq!(list.items[0].type_meta.kind, "ConfigMap");
+    assert_eq!(list.items[0].type_meta.api_version, "v1");
+    assert_eq!(client.list_all_namespaces_resource::<ConfigMap>().unwrap().items.len(), 2);
+
+    let error = client.get_namespaced_resource::<ConfigMap>("default", "missing").unwrap_err();
+    assert_eq!(error.status_code().map(|status| status.as_u16()), Some(404));
+}
+
+#[test]
+fn replace_and_conflicts() {
+    let server = FakeApiServer::start().unwrap();
+    let client = server.client().unwrap();
+
+    let created = client.create_namespaced_resource("default", &config_map("settings", "a")).unwrap();
+    assert!(client.create_namespaced_resource("default", &config_map("settings", "a")).unwrap_err().is_conflict());
+
+    let mut update = config_map("settings", "b");
+    update.metadata.resource_version = created.metadata.resource_version.clone();
+    let replaced = client.replace_namespaced_resource("default", "settings", &update).unwrap();
+    assert_eq!(replaced.data["value"], "b");
+    assert_eq!(replaced.metadata.uid, created.metadata.uid);
+    assert_ne!(replaced.metadata.resource_version, created.metadata.resource_version);
+
+    // The object was modified since `created` was read
+    let mut stale = config_map("settings", "c");
+    stale.metadata.resource_version = created.metadata.resource_version;
+    assert!(client.replace_namespaced_resource("default", "settings", &stale).unwrap_err().is_conflict());
+    let current: ConfigMap = client.get_namespaced_resource("default", "settings").unwrap();
+    assert_eq!(current.data["value"], "b");
+}
+
+#[test]
+fn kind_without_type_meta() {
+    let server = FakeApiServer::start().unwrap();
+    let client = server.client().unwrap();
+
+    let deployment: Deployment = serde_json::from_value(json!({
+        "metadata": { "name": "web" },
+        "spec": {
+            "selector": { "matchLabels": { "app": "web" } },
+            "template": {
+                "metadata": { "labels": { "app": "web" } },
+                "spec": { "containers": [{ "name": "nginx", "image": "nginx" }] },
+            },
+        },
+    })).unwrap();
+    let created = client.create_namespaced_resource("default", &deployment).unwrap();
+    assert_eq!(created.type_meta.kind, "Deployment");
+    assert_eq!(created.type_meta.api_version, "apps/v1");
+    assert_eq!(client.list_namespaced_resource::<Deployment>("default").unwrap().type_meta.kind, "DeploymentList");
+}
+
+#[test]
+fn watch() {
+    let server = FakeApiServer::start().unwrap();
+    let client = server.client().unwrap();
+
+    client.create_namespaced_resource("default", &config_map("before", "a")).unwrap();
+    let resource_version = server.resource_version().to_string();
+    let mut events = client.watch_namespaced_resource::<ConfigMap>("default", Some(&resource_version)).unwrap();
+
+    let created = client.create_namespaced_resource("default", &config_map("settings", "a")).unwrap();
+    client.create_namespaced_resource("other", &config_map("elsewhere", "a")).unwrap();
+    let mut update = config_map("settings", "b");
+    update.metadata.resource_version = created.metadata.resource_version;
+    client.replace_namespaced_resource("default", "settings", &update).unwrap();
+    client.delete_namespaced_resource::<ConfigMap>("default", "settings").unwrap();
+
+    match events.next() {
+        Some(Ok(WatchEvent::Added(config_map))) => {
+            assert_eq!(config_map.metadata.name, "settings");
+            assert_eq!(config_map.type_meta.kind, "ConfigMap");
+        },
+        event => panic!("expected the creation, got {:?}", event),
+    }
+    match events.next() {
+        Some(Ok(WatchEvent::Modified(config_map))) => assert_eq!(config_map.data["value"], "b"),
+        event => panic!("expected the update, got {:?}", event),
+    }
+    match events.next() {
+        Some(Ok(WatchEvent::Deleted(config_map))) => assert_eq!(config_map.metadata.name, "settings"),
+        event => panic!("expected the deletion, got {:?}", event),
+    }
+}
@@ -0,0 +1,118 @@
+//! Objects read from the API server have to serialize back to the same JSON, including the
+//! fields that aren't modeled, so that replacing them doesn't drop anything.
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::de::{self, Deserialize, DeserializeOwned, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use kubeclient::api::apiextensions::v1::CustomResourceDefinition;
+use kubeclient::api::apps::v1::Deployment;
+use kubeclient::api::core::v1::{Node, Pod, VolumeSource};
+
+// JSON that fails to parse if an object has a key twice, which `Value` silently merges
+struct Strict(Value);
+
+impl<'de> Deserialize<'de> for Strict {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Strict, D::Error> {
+        deserializer.deserialize_any(StrictVisitor)
+    }
+}
+
+struct StrictVisitor;
+
+impl<'de> Visitor<'de> for StrictVisitor {
+    type Value = Strict;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("JSON without duplicate keys")
+    }
+
+    fn visit_bool<E>(self, value: bool) -> Result<Strict, E> {
+        Ok(Strict(Value::from(value)))
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Strict, E> {
+        Ok(Strict(Value::from(value)))
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Strict, E> {
+        Ok(Strict(Value::from(value)))
+    }
+
+    fn visit_f64<E>(self, value: f64) -> Result<Strict, E> {
+        Ok(Strict(Value::from(value)))
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Strict, E> {
+        Ok(Strict(Value::from(value)))
+    }
+
+    fn visit_unit<E>(self) -> Result<Strict, E> {
+        Ok(Strict(Value::Null))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Strict, A::Error> {
+        let mut values = Vec::new();
+        while let Some(Strict(value)) = seq.next_element()? {
+            values.push(value);
+        }
+        Ok(Strict(Value::Array(values)))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Strict, A::Error> {
+        let mut values = BTreeMap::new();
+        while let Some((key, Strict(value))) = map.next_entry::<String, Strict>()? {
+            if values.contains_key(&key) {
+                return Err(de::Error::custom(format!("duplicate key `{}`", key)));
+            }
+            values.insert(key, value);
+        }
+        Ok(Strict(Value::Object(values.into_iter().collect())))
+    }
+}
+
+// Serialize directly, rejecting duplicate keys, and sort the keys for comparison
+fn reserialize<T: Serialize>(object: &T) -> String {
+    let json = serde_json::to_string(object).unwrap();
+    let Strict(value) = serde_json::from_str(&json).unwrap();
+    serde_json::to_string_pretty(&value).unwrap()
+}
+
+fn assert_round_trip<T: Serialize + DeserializeOwned>(fixture: &str) {
+    let original: Value = serde_json::from_str(fixture).unwrap();
+    let object: T = serde_json::from_str(fixture).unwrap();
+    assert_eq!(reserialize(&object), serde_json::to_string_pretty(&original).unwrap());
+}
+
+#[test]
+fn pod() {
+    assert_round_trip::<Pod>(include_str!("fixtures/pod.json"));
+}
+
+#[test]
+fn deployment() {
+    assert_round_trip::<Deployment>(include_str!("fixtures/deployment.json"));
+}
+
+#[test]
+fn node() {
+    assert_round_trip::<Node>(include_str!("fixtures/node.json"));
+}
+
+#[test]
+fn custom_resource_definition() {
+    assert_round_trip::<CustomResourceDefinition>(include_str!("fixtures/crd.json"));
+}
+
+#[test]
+fn modified_volume_source() {
+    let mut pod: Pod = serde_json::from_str(include_str!("fixtures/pod.json")).unwrap();
+    pod.spec.volumes[0].source = VolumeSource::HostPath { path: "/data".to_string(), type_: None };
+    let mut expected: Value = serde_json::from_str(include_str!("fixtures/pod.json")).unwrap();
+    let volume = expected["spec"]["volumes"][0].as_object_mut().unwrap();
+    volume.remove("projected");
+    volume.insert("hostPath".to_string(), json!({ "path": "/data" }));
+    assert_eq!(reserialize(&pod), serde_json::to_string_pretty(&expected).unwrap());
+}